@@ -0,0 +1,11 @@
+/// Alignment of children along an axis of a container.
+///
+/// The `Start` is the side where reading begins: left for horizontal axis and top for vertical one.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Default)]
+#[allow(dead_code)]
+pub enum Align {
+    #[default]
+    Start,
+    Center,
+    End,
+}
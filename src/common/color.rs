@@ -1,3 +1,5 @@
+/// The RGBA colour. RGB channels are sRGB encoded, as colours from image editors and CSS are.
+/// Alpha is always linear.
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub struct Color(pub f32, pub f32, pub f32, pub f32);
 
@@ -9,12 +11,248 @@ impl Color {
     pub fn green() -> Self { Color(0.0, 1.0, 0.0, 1.0) }
     pub fn blue() -> Self { Color(0.0, 0.0, 1.0, 1.0) }
 
+    /// Makes the colour from sRGB encoded channels.
     pub fn rgb(r: f32, g: f32, b: f32) -> Self { Color(r, g, b, 1.0) }
 
+    /// The same as `rgb`, but states the colour space explicitly.
+    pub fn srgb(r: f32, g: f32, b: f32) -> Self { Color::rgb(r, g, b) }
+
+    /// Makes the colour from linear light channels.
+    pub fn linear_rgb(r: f32, g: f32, b: f32) -> Self { Color::linear_rgba(r, g, b, 1.0) }
+
+    pub fn linear_rgba(r: f32, g: f32, b: f32, a: f32) -> Self {
+        Color(r, g, b, a).to_srgb()
+    }
+
+    /// Decodes sRGB channels to linear light. The result is only meant for GL.
+    pub fn to_linear(self) -> Self {
+        let f = |c: f32| {
+            if c <= 0.04045 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        };
+
+        Color(f(self.0), f(self.1), f(self.2), self.3)
+    }
+
+    /// Encodes linear light channels to sRGB.
+    pub fn to_srgb(self) -> Self {
+        let f = |c: f32| {
+            if c <= 0.0031308 {
+                c * 12.92
+            } else {
+                1.055 * c.powf(1.0 / 2.4) - 0.055
+            }
+        };
+
+        Color(f(self.0), f(self.1), f(self.2), self.3)
+    }
+
     pub fn r(self) -> f32 { self.0 }
     pub fn g(self) -> f32 { self.1 }
     pub fn b(self) -> f32 { self.2 }
     pub fn a(self) -> f32 { self.3 }
+
+    pub fn from_rgba8(r: u8, g: u8, b: u8, a: u8) -> Self {
+        let f = |c: u8| c as f32 / 255.0;
+        Color(f(r), f(g), f(b), f(a))
+    }
+
+    pub fn from_rgb8(r: u8, g: u8, b: u8) -> Self { Color::from_rgba8(r, g, b, u8::MAX) }
+
+    /// Returns channels clamped to `[0, 1]` and scaled to `[0, 255]`.
+    pub fn to_rgba8(self) -> [u8; 4] {
+        let f = |c: f32| (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+        [f(self.0), f(self.1), f(self.2), f(self.3)]
+    }
+
+    /// Parses the `RGB`, `RGBA`, `RRGGBB` or `RRGGBBAA` hex string. The leading `#` is optional.
+    pub fn from_hex(hex: &str) -> Result<Self, ColorError> {
+        let hex = hex.strip_prefix('#').unwrap_or(hex);
+
+        let digits: Vec<u8> = hex
+            .chars()
+            .map(|ch| ch.to_digit(16).map(|d| d as u8))
+            .collect::<Option<_>>()
+            .ok_or(ColorError::WrongHexDigit)?;
+
+        let channels: Vec<u8> = match digits.len() {
+            3 | 4 => digits.iter().map(|d| d * 16 + d).collect(),
+            6 | 8 => digits.chunks(2).map(|d| d[0] * 16 + d[1]).collect(),
+            _ => return Err(ColorError::WrongHexLength),
+        };
+
+        let alpha = channels.get(3).cloned().unwrap_or(u8::MAX);
+        Ok(Color::from_rgba8(channels[0], channels[1], channels[2], alpha))
+    }
+
+    /// Returns the CSS named colour. The name is case insensitive.
+    ///
+    /// Note that CSS names differ from the constructors: the CSS `green` is `#008000`,
+    /// but the `Color::green` is `#00ff00` (CSS `lime`).
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.to_ascii_lowercase();
+
+        CSS_COLORS
+            .binary_search_by(|(css, _)| (*css).cmp(name.as_str()))
+            .ok()
+            .map(|idx| {
+                let rgb = CSS_COLORS[idx].1;
+                Color::from_rgb8((rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8)
+            })
+    }
+
+    /// Makes the colour from hue in degrees, saturation and lightness in `[0, 1]`.
+    pub fn hsl(h: f32, s: f32, l: f32) -> Self { Color::hsla(h, s, l, 1.0) }
+
+    pub fn hsla(h: f32, s: f32, l: f32, a: f32) -> Self {
+        let chroma = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        Color::from_hue(h, chroma, l - chroma / 2.0, a)
+    }
+
+    /// Makes the colour from hue in degrees, saturation and value in `[0, 1]`.
+    pub fn hsv(h: f32, s: f32, v: f32) -> Self { Color::hsva(h, s, v, 1.0) }
+
+    pub fn hsva(h: f32, s: f32, v: f32, a: f32) -> Self {
+        let chroma = v * s;
+        Color::from_hue(h, chroma, v - chroma, a)
+    }
+
+    fn from_hue(h: f32, chroma: f32, min: f32, a: f32) -> Self {
+        let h = h.rem_euclid(360.0) / 60.0;
+        let x = chroma * (1.0 - (h % 2.0 - 1.0).abs());
+
+        let (r, g, b) = match h as i32 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+
+        Color(r + min, g + min, b + min, a)
+    }
+
+    /// Returns hue in degrees `[0, 360)`, saturation and lightness.
+    pub fn to_hsl(self) -> (f32, f32, f32) {
+        let (max, min) = self.max_min();
+        let l = (max + min) / 2.0;
+        let d = max - min;
+
+        let s = if d == 0.0 { 0.0 } else { d / (1.0 - (2.0 * l - 1.0).abs()) };
+
+        (self.hue(), s, l)
+    }
+
+    /// Returns hue in degrees `[0, 360)`, saturation and value.
+    pub fn to_hsv(self) -> (f32, f32, f32) {
+        let (max, min) = self.max_min();
+        let s = if max == 0.0 { 0.0 } else { (max - min) / max };
+
+        (self.hue(), s, max)
+    }
+
+    pub fn with_alpha(self, a: f32) -> Self { Color(self.0, self.1, self.2, a) }
+
+    /// Linear interpolation from `self` (`t` = 0) to `rhs` (`t` = 1), including alpha.
+    pub fn lerp(self, rhs: Color, t: f32) -> Self {
+        let f = |a: f32, b: f32| a + (b - a) * t;
+        Color(f(self.0, rhs.0), f(self.1, rhs.1), f(self.2, rhs.2), f(self.3, rhs.3))
+    }
+
+    /// Increases the HSL lightness by `amount`.
+    pub fn lighten(self, amount: f32) -> Self {
+        let (h, s, l) = self.to_hsl();
+        Color::hsla(h, s, (l + amount).clamp(0.0, 1.0), self.3)
+    }
+
+    /// Decreases the HSL lightness by `amount`.
+    pub fn darken(self, amount: f32) -> Self { self.lighten(-amount) }
+
+    /// Increases the HSL saturation by `amount`. A negative `amount` desaturates the colour.
+    pub fn saturate(self, amount: f32) -> Self {
+        let (h, s, l) = self.to_hsl();
+        Color::hsla(h, (s + amount).clamp(0.0, 1.0), l, self.3)
+    }
+
+    /// Returns the colour with RGB channels multiplied by alpha.
+    pub fn premultiplied(self) -> Self {
+        Color(self.0 * self.3, self.1 * self.3, self.2 * self.3, self.3)
+    }
+
+    fn max_min(self) -> (f32, f32) {
+        let Color(r, g, b, _) = self;
+        (r.max(g).max(b), r.min(g).min(b))
+    }
+
+    fn hue(self) -> f32 {
+        let Color(r, g, b, _) = self;
+        let (max, min) = self.max_min();
+        let d = max - min;
+
+        let h = if d == 0.0 {
+            0.0
+        } else if max == r {
+            (g - b) / d
+        } else if max == g {
+            (b - r) / d + 2.0
+        } else {
+            (r - g) / d + 4.0
+        };
+
+        (h * 60.0).rem_euclid(360.0)
+    }
+}
+
+/// Component-wise multiplication, including alpha.
+impl std::ops::Mul for Color {
+    type Output = Color;
+
+    fn mul(self, rhs: Color) -> Self::Output {
+        Color(self.0 * rhs.0, self.1 * rhs.1, self.2 * rhs.2, self.3 * rhs.3)
+    }
+}
+
+impl std::ops::MulAssign for Color {
+    fn mul_assign(&mut self, rhs: Color) { *self = *self * rhs }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum ColorError {
+    WrongHexLength,
+    WrongHexDigit,
+    UnknownName,
+}
+
+impl std::str::FromStr for Color {
+    type Err = ColorError;
+
+    /// Parses a hex string starting with `#` or a CSS colour name.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        if s.starts_with('#') {
+            Color::from_hex(s)
+        } else {
+            Color::from_name(s).ok_or(ColorError::UnknownName)
+        }
+    }
+}
+
+/// Formats the colour as `#rrggbb`, or `#rrggbbaa` if it's not opaque.
+impl std::fmt::Display for Color {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let [r, g, b, a] = self.to_rgba8();
+
+        if a == u8::MAX {
+            write!(f, "#{:02x}{:02x}{:02x}", r, g, b)
+        } else {
+            write!(f, "#{:02x}{:02x}{:02x}{:02x}", r, g, b, a)
+        }
+    }
 }
 
 impl Default for Color {
@@ -44,3 +282,289 @@ impl From<glm::Vec3> for Color {
 impl From<glm::Vec4> for Color {
     fn from(v: glm::Vec4) -> Self { Color(v.x, v.y, v.z, v.w) }
 }
+
+/// CSS named colours sorted by name.
+const CSS_COLORS: &[(&str, u32)] = &[
+    ("aliceblue", 0xf0f8ff),
+    ("antiquewhite", 0xfaebd7),
+    ("aqua", 0x00ffff),
+    ("aquamarine", 0x7fffd4),
+    ("azure", 0xf0ffff),
+    ("beige", 0xf5f5dc),
+    ("bisque", 0xffe4c4),
+    ("black", 0x000000),
+    ("blanchedalmond", 0xffebcd),
+    ("blue", 0x0000ff),
+    ("blueviolet", 0x8a2be2),
+    ("brown", 0xa52a2a),
+    ("burlywood", 0xdeb887),
+    ("cadetblue", 0x5f9ea0),
+    ("chartreuse", 0x7fff00),
+    ("chocolate", 0xd2691e),
+    ("coral", 0xff7f50),
+    ("cornflowerblue", 0x6495ed),
+    ("cornsilk", 0xfff8dc),
+    ("crimson", 0xdc143c),
+    ("cyan", 0x00ffff),
+    ("darkblue", 0x00008b),
+    ("darkcyan", 0x008b8b),
+    ("darkgoldenrod", 0xb8860b),
+    ("darkgray", 0xa9a9a9),
+    ("darkgreen", 0x006400),
+    ("darkgrey", 0xa9a9a9),
+    ("darkkhaki", 0xbdb76b),
+    ("darkmagenta", 0x8b008b),
+    ("darkolivegreen", 0x556b2f),
+    ("darkorange", 0xff8c00),
+    ("darkorchid", 0x9932cc),
+    ("darkred", 0x8b0000),
+    ("darksalmon", 0xe9967a),
+    ("darkseagreen", 0x8fbc8f),
+    ("darkslateblue", 0x483d8b),
+    ("darkslategray", 0x2f4f4f),
+    ("darkslategrey", 0x2f4f4f),
+    ("darkturquoise", 0x00ced1),
+    ("darkviolet", 0x9400d3),
+    ("deeppink", 0xff1493),
+    ("deepskyblue", 0x00bfff),
+    ("dimgray", 0x696969),
+    ("dimgrey", 0x696969),
+    ("dodgerblue", 0x1e90ff),
+    ("firebrick", 0xb22222),
+    ("floralwhite", 0xfffaf0),
+    ("forestgreen", 0x228b22),
+    ("fuchsia", 0xff00ff),
+    ("gainsboro", 0xdcdcdc),
+    ("ghostwhite", 0xf8f8ff),
+    ("gold", 0xffd700),
+    ("goldenrod", 0xdaa520),
+    ("gray", 0x808080),
+    ("green", 0x008000),
+    ("greenyellow", 0xadff2f),
+    ("grey", 0x808080),
+    ("honeydew", 0xf0fff0),
+    ("hotpink", 0xff69b4),
+    ("indianred", 0xcd5c5c),
+    ("indigo", 0x4b0082),
+    ("ivory", 0xfffff0),
+    ("khaki", 0xf0e68c),
+    ("lavender", 0xe6e6fa),
+    ("lavenderblush", 0xfff0f5),
+    ("lawngreen", 0x7cfc00),
+    ("lemonchiffon", 0xfffacd),
+    ("lightblue", 0xadd8e6),
+    ("lightcoral", 0xf08080),
+    ("lightcyan", 0xe0ffff),
+    ("lightgoldenrodyellow", 0xfafad2),
+    ("lightgray", 0xd3d3d3),
+    ("lightgreen", 0x90ee90),
+    ("lightgrey", 0xd3d3d3),
+    ("lightpink", 0xffb6c1),
+    ("lightsalmon", 0xffa07a),
+    ("lightseagreen", 0x20b2aa),
+    ("lightskyblue", 0x87cefa),
+    ("lightslategray", 0x778899),
+    ("lightslategrey", 0x778899),
+    ("lightsteelblue", 0xb0c4de),
+    ("lightyellow", 0xffffe0),
+    ("lime", 0x00ff00),
+    ("limegreen", 0x32cd32),
+    ("linen", 0xfaf0e6),
+    ("magenta", 0xff00ff),
+    ("maroon", 0x800000),
+    ("mediumaquamarine", 0x66cdaa),
+    ("mediumblue", 0x0000cd),
+    ("mediumorchid", 0xba55d3),
+    ("mediumpurple", 0x9370db),
+    ("mediumseagreen", 0x3cb371),
+    ("mediumslateblue", 0x7b68ee),
+    ("mediumspringgreen", 0x00fa9a),
+    ("mediumturquoise", 0x48d1cc),
+    ("mediumvioletred", 0xc71585),
+    ("midnightblue", 0x191970),
+    ("mintcream", 0xf5fffa),
+    ("mistyrose", 0xffe4e1),
+    ("moccasin", 0xffe4b5),
+    ("navajowhite", 0xffdead),
+    ("navy", 0x000080),
+    ("oldlace", 0xfdf5e6),
+    ("olive", 0x808000),
+    ("olivedrab", 0x6b8e23),
+    ("orange", 0xffa500),
+    ("orangered", 0xff4500),
+    ("orchid", 0xda70d6),
+    ("palegoldenrod", 0xeee8aa),
+    ("palegreen", 0x98fb98),
+    ("paleturquoise", 0xafeeee),
+    ("palevioletred", 0xdb7093),
+    ("papayawhip", 0xffefd5),
+    ("peachpuff", 0xffdab9),
+    ("peru", 0xcd853f),
+    ("pink", 0xffc0cb),
+    ("plum", 0xdda0dd),
+    ("powderblue", 0xb0e0e6),
+    ("purple", 0x800080),
+    ("rebeccapurple", 0x663399),
+    ("red", 0xff0000),
+    ("rosybrown", 0xbc8f8f),
+    ("royalblue", 0x4169e1),
+    ("saddlebrown", 0x8b4513),
+    ("salmon", 0xfa8072),
+    ("sandybrown", 0xf4a460),
+    ("seagreen", 0x2e8b57),
+    ("seashell", 0xfff5ee),
+    ("sienna", 0xa0522d),
+    ("silver", 0xc0c0c0),
+    ("skyblue", 0x87ceeb),
+    ("slateblue", 0x6a5acd),
+    ("slategray", 0x708090),
+    ("slategrey", 0x708090),
+    ("snow", 0xfffafa),
+    ("springgreen", 0x00ff7f),
+    ("steelblue", 0x4682b4),
+    ("tan", 0xd2b48c),
+    ("teal", 0x008080),
+    ("thistle", 0xd8bfd8),
+    ("tomato", 0xff6347),
+    ("turquoise", 0x40e0d0),
+    ("violet", 0xee82ee),
+    ("wheat", 0xf5deb3),
+    ("white", 0xffffff),
+    ("whitesmoke", 0xf5f5f5),
+    ("yellow", 0xffff00),
+    ("yellowgreen", 0x9acd32),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: Color, b: Color) {
+        let close = |x: f32, y: f32| (x - y).abs() < 1e-4;
+        assert!(close(a.0, b.0) && close(a.1, b.1) && close(a.2, b.2) && close(a.3, b.3),
+                "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn hex() {
+        assert_eq!(Color::from_hex("#ff0000"), Ok(Color::red()));
+        assert_eq!(Color::from_hex("00FF00"), Ok(Color::green()));
+        assert_eq!(Color::from_hex("#00f"), Ok(Color::blue()));
+        assert_eq!(Color::from_hex("#0000"), Ok(Color(0.0, 0.0, 0.0, 0.0)));
+        assert_eq!(Color::from_hex("#ffffff80").unwrap().to_rgba8(), [255, 255, 255, 128]);
+
+        assert_eq!(Color::from_hex("#fffff"), Err(ColorError::WrongHexLength));
+        assert_eq!(Color::from_hex(""), Err(ColorError::WrongHexLength));
+        assert_eq!(Color::from_hex("#ggg"), Err(ColorError::WrongHexDigit));
+    }
+
+    #[test]
+    fn from_str() {
+        assert_eq!("#102030".parse(), Ok(Color::from_rgb8(0x10, 0x20, 0x30)));
+        assert_eq!(" Red ".parse(), Ok(Color::red()));
+        assert_eq!("green".parse(), Ok(Color::from_rgb8(0, 128, 0)));
+        assert_eq!("rebeccapurple".parse(), Ok(Color::from_rgb8(0x66, 0x33, 0x99)));
+        assert_eq!("nope".parse::<Color>(), Err(ColorError::UnknownName));
+    }
+
+    #[test]
+    fn css_colors_sorted() {
+        assert!(CSS_COLORS.windows(2).all(|w| w[0].0 < w[1].0));
+    }
+
+    #[test]
+    fn rgba8_round_trip() {
+        for &c in &[0, 1, 127, 128, 254, 255] {
+            let color = Color::from_rgba8(c, 255 - c, c / 2, 255);
+            assert_eq!(color.to_rgba8(), [c, 255 - c, c / 2, 255]);
+        }
+
+        assert_eq!(Color(2.0, -1.0, 0.5, 1.0).to_rgba8(), [255, 0, 128, 255]);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Color::rgb(1.0, 0.5, 0.0).to_string(), "#ff8000");
+        assert_eq!(Color(0.0, 0.0, 0.0, 0.0).to_string(), "#00000000");
+
+        let color = Color::from_rgba8(0x12, 0x34, 0x56, 0x78);
+        assert_eq!(color.to_string().parse(), Ok(color));
+    }
+
+    #[test]
+    fn hsl() {
+        assert_close(Color::hsl(0.0, 1.0, 0.5), Color::red());
+        assert_close(Color::hsl(120.0, 1.0, 0.5), Color::green());
+        assert_close(Color::hsl(240.0, 1.0, 0.5), Color::blue());
+        assert_close(Color::hsl(-120.0, 1.0, 0.5), Color::blue());
+        assert_close(Color::hsl(0.0, 0.0, 0.5), Color::rgb(0.5, 0.5, 0.5));
+        assert_close(Color::hsl(30.0, 1.0, 0.25), Color::rgb(0.5, 0.25, 0.0));
+    }
+
+    #[test]
+    fn hsv() {
+        assert_close(Color::hsv(60.0, 1.0, 1.0), Color::rgb(1.0, 1.0, 0.0));
+        assert_close(Color::hsv(300.0, 0.5, 0.8), Color::rgb(0.8, 0.4, 0.8));
+        assert_close(Color::hsv(0.0, 0.0, 0.0), Color::black());
+    }
+
+    #[test]
+    fn hsl_hsv_round_trip() {
+        for i in 0..64_u32 {
+            let color = Color::from_rgba8((i * 4) as u8, (255 - i * 3) as u8, (i * 37 % 256) as u8, 200);
+
+            let (h, s, l) = color.to_hsl();
+            assert_close(Color::hsla(h, s, l, color.a()), color);
+
+            let (h, s, v) = color.to_hsv();
+            assert_close(Color::hsva(h, s, v, color.a()), color);
+
+            assert!((0.0..360.0).contains(&h));
+        }
+    }
+
+    #[test]
+    fn mul_and_alpha() {
+        let color = Color(1.0, 0.5, 0.2, 0.5);
+
+        assert_eq!(color * Color::white(), color);
+        assert_eq!(color * Color(0.5, 0.5, 0.5, 0.5), Color(0.5, 0.25, 0.1, 0.25));
+        assert_eq!(color.with_alpha(1.0), Color::rgb(1.0, 0.5, 0.2));
+        assert_eq!(color.premultiplied(), Color(0.5, 0.25, 0.1, 0.5));
+    }
+
+    #[test]
+    fn lerp() {
+        let a = Color(0.0, 0.0, 0.0, 0.0);
+        let b = Color::white();
+
+        assert_eq!(a.lerp(b, 0.0), a);
+        assert_eq!(a.lerp(b, 1.0), b);
+        assert_eq!(a.lerp(b, 0.25), Color(0.25, 0.25, 0.25, 0.25));
+    }
+
+    #[test]
+    fn lighten_darken_saturate() {
+        let red = Color::red();
+
+        assert_close(red.lighten(0.25), Color::rgb(1.0, 0.5, 0.5));
+        assert_close(red.darken(0.25), Color::rgb(0.5, 0.0, 0.0));
+        assert_close(red.darken(1.0), Color::black());
+        assert_close(red.saturate(-1.0), Color::rgb(0.5, 0.5, 0.5));
+        assert_close(Color::hsl(200.0, 0.5, 0.5).saturate(0.25), Color::hsl(200.0, 0.75, 0.5));
+        assert_eq!(red.with_alpha(0.3).lighten(0.1).a(), 0.3);
+    }
+
+    #[test]
+    fn linear_round_trip() {
+        assert_eq!(Color::white().to_linear(), Color::white());
+        assert_eq!(Color::black().to_linear(), Color::black());
+        assert_close(Color::rgb(0.5, 0.5, 0.5).to_linear(), Color::rgb(0.2140, 0.2140, 0.2140));
+        assert_close(Color::linear_rgb(0.2140, 0.0, 1.0), Color::rgb(0.5, 0.0, 1.0));
+
+        for i in 0..=255 {
+            let color = Color::from_rgba8(i, i, i, i);
+            assert_eq!(color.to_linear().to_srgb().to_rgba8(), color.to_rgba8());
+        }
+    }
+}
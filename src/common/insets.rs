@@ -0,0 +1,61 @@
+use super::Vec2d;
+
+/// Distances from every side of a rect.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Default)]
+pub struct Insets<T> {
+    pub left: T,
+    pub right: T,
+    pub bot: T,
+    pub top: T,
+}
+
+#[allow(dead_code)]
+impl<T> Insets<T>
+    where
+        T: Copy + num::Num,
+{
+    pub fn new(left: T, right: T, bot: T, top: T) -> Self { Insets { left, right, bot, top } }
+
+    pub fn uniform(value: T) -> Self { Insets::new(value, value, value, value) }
+
+    pub fn symmetric(horizontal: T, vertical: T) -> Self {
+        Insets::new(horizontal, horizontal, vertical, vertical)
+    }
+
+    pub fn zero() -> Self { Insets::uniform(T::zero()) }
+
+    pub fn horizontal(&self) -> T { self.left + self.right }
+
+    pub fn vertical(&self) -> T { self.bot + self.top }
+
+    /// Returns the total size the insets add to a rect.
+    pub fn size(&self) -> Vec2d<T> { Vec2d::new(self.horizontal(), self.vertical()) }
+}
+
+impl<T> From<T> for Insets<T>
+    where
+        T: Copy + num::Num,
+{
+    fn from(value: T) -> Self { Insets::uniform(value) }
+}
+
+impl<T> From<Vec2d<T>> for Insets<T>
+    where
+        T: Copy + num::Num,
+{
+    fn from(v: Vec2d<T>) -> Self { Insets::symmetric(v.x, v.y) }
+}
+
+impl<T> From<(T, T)> for Insets<T>
+    where
+        T: Copy + num::Num,
+{
+    fn from((h, v): (T, T)) -> Self { Insets::symmetric(h, v) }
+}
+
+impl<T> From<(T, T, T, T)> for Insets<T>
+    where
+        T: Copy + num::Num,
+{
+    fn from((left, right, bot, top): (T, T, T, T)) -> Self { Insets::new(left, right, bot, top) }
+}
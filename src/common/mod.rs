@@ -1,9 +1,13 @@
+mod align;
 mod color;
+mod insets;
 mod position;
 mod rect;
 mod vec2d;
 
+pub use align::Align;
 pub use color::Color;
-pub use position::Position;
+pub use insets::Insets;
+pub use position::{Position, Anchor};
 pub use rect::Rect;
 pub use vec2d::Vec2d;
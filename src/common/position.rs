@@ -3,8 +3,77 @@ use super::{
     Vec2d,
 };
 
+/// Fractional placement of a child in a frame.
+///
+/// The `pivot` point of the child is placed at the `anchor` point of the frame.
+/// Both points are fractions of the size: `(0.0, 0.0)` is the bottom left corner
+/// and `(1.0, 1.0)` is the top right one.
 #[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Anchor {
+    pub anchor: Vec2d<f32>,
+    pub pivot: Vec2d<f32>,
+    /// The offset in fractions of the frame size.
+    pub offset: Vec2d<f32>,
+    /// The offset in pixels.
+    pub pad: Vec2d<i32>,
+}
+
+#[allow(dead_code)]
+impl Anchor {
+    pub fn new<A, P>(anchor: A, pivot: P) -> Self
+        where
+            A: Into<Vec2d<f32>>,
+            P: Into<Vec2d<f32>>,
+    {
+        Anchor {
+            anchor: anchor.into(),
+            pivot: pivot.into(),
+            offset: Vec2d::new(0.0, 0.0),
+            pad: Vec2d::new(0, 0),
+        }
+    }
+
+    pub fn offset<O>(mut self, offset: O) -> Self
+        where
+            O: Into<Vec2d<f32>>,
+    {
+        self.offset = offset.into();
+        self
+    }
+
+    pub fn pad<P>(mut self, pad: P) -> Self
+        where
+            P: Into<Vec2d<i32>>,
+    {
+        self.pad = pad.into();
+        self
+    }
+
+    /// Returns the position of the child relative to the frame's bottom left corner.
+    pub fn place<F, S>(&self, frame: F, size: S) -> Vec2d<i32>
+        where
+            F: Into<Vec2d<i32>>,
+            S: Into<Vec2d<i32>>,
+    {
+        let frame = frame.into();
+        let size = size.into();
+
+        Vec2d::new(
+            fraction_of(frame.x, self.anchor.x + self.offset.x) - fraction_of(size.x, self.pivot.x),
+            fraction_of(frame.y, self.anchor.y + self.offset.y) - fraction_of(size.y, self.pivot.y),
+        ) + self.pad
+    }
+}
+
+/// Rounds to the nearest pixel. Halves are rounded down, so the center of the odd length
+/// is the same as with integer division.
+fn fraction_of(length: i32, fraction: f32) -> i32 {
+    (length as f32 * fraction - 0.5).ceil() as i32
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
 pub enum Position {
+    #[default]
     Center,
     Left(i32),
     Right(i32),
@@ -14,40 +83,129 @@ pub enum Position {
     LeftTop(i32, i32),
     RightBot(i32, i32),
     RightTop(i32, i32),
+    Anchor(Anchor),
 }
 
+#[allow(dead_code)]
 impl Position {
+    /// Places the child's `pivot` at the frame's `anchor`.
+    pub fn anchored<A, P>(anchor: A, pivot: P) -> Self
+        where
+            A: Into<Vec2d<f32>>,
+            P: Into<Vec2d<f32>>,
+    { Position::Anchor(Anchor::new(anchor, pivot)) }
+
+    /// Places the child at fractions of the frame, so that `(0.0, 0.0)` is the bottom left
+    /// corner and `(1.0, 1.0)` is the top right one. The child never leaves the frame.
+    pub fn percent(x: f32, y: f32) -> Self { Position::anchored((x, y), (x, y)) }
+
+    /// Returns the `Anchor` equivalent of the position.
+    pub fn anchor(self) -> Anchor {
+        match self {
+            Position::Center => Anchor::new((0.5, 0.5), (0.5, 0.5)),
+            Position::Left(pad) => Anchor::new((0.0, 0.5), (0.0, 0.5)).pad((pad, 0)),
+            Position::Right(pad) => Anchor::new((1.0, 0.5), (1.0, 0.5)).pad((-pad, 0)),
+            Position::Bot(pad) => Anchor::new((0.5, 0.0), (0.5, 0.0)).pad((0, pad)),
+            Position::Top(pad) => Anchor::new((0.5, 1.0), (0.5, 1.0)).pad((0, -pad)),
+            Position::LeftBot(l, b) => Anchor::new((0.0, 0.0), (0.0, 0.0)).pad((l, b)),
+            Position::LeftTop(l, t) => Anchor::new((0.0, 1.0), (0.0, 1.0)).pad((l, -t)),
+            Position::RightBot(r, b) => Anchor::new((1.0, 0.0), (1.0, 0.0)).pad((-r, b)),
+            Position::RightTop(r, t) => Anchor::new((1.0, 1.0), (1.0, 1.0)).pad((-r, -t)),
+            Position::Anchor(anchor) => anchor,
+        }
+    }
+
     pub fn rect<S>(self, frame: Rect<i32>, size: S) -> Rect<i32>
         where
             S: Into<Vec2d<i32>>,
     {
         let size = size.into();
+        let pos = self.anchor().place(frame.size(), size);
 
-        let pos = match self {
-            Position::Center => frame.size().half() - size.half(),
-            Position::Left(pad) => Vec2d::new(pad, frame.height / 2 - size.y / 2),
-            Position::Right(pad) => Vec2d::new(
-                frame.width - size.x - pad,
-                frame.height / 2 - size.y / 2,
-            ),
-            Position::Bot(pad) => Vec2d::new(frame.width / 2 - size.x / 2, pad),
-            Position::Top(pad) => Vec2d::new(
-                frame.width / 2 - size.x / 2,
-                frame.height - size.y - pad,
-            ),
-            Position::LeftBot(l, b) => Vec2d::new(l, b),
-            Position::LeftTop(l, t) => Vec2d::new(l, frame.height - size.y - t),
-            Position::RightBot(r, b) => Vec2d::new(frame.width - size.x - r, b),
-            Position::RightTop(r, t) => Vec2d::new(
-                frame.width - size.x - r,
-                frame.height - size.y - t,
-            ),
-        };
-
-        Rect::new(pos, size)
+        Rect::new(frame.pos() + pos, size)
     }
 }
 
-impl Default for Position {
-    fn default() -> Self { Position::Center }
+impl From<Anchor> for Position {
+    fn from(anchor: Anchor) -> Self { Position::Anchor(anchor) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(w: i32, h: i32) -> Rect<i32> { Rect::new((0, 0), (w, h)) }
+
+    #[test]
+    fn shorthands() {
+        let f = frame(11, 7);
+        let s = Vec2d::new(4, 3);
+
+        let pos = |p: Position| p.rect(f, s).pos().into_inner();
+
+        assert_eq!(pos(Position::Center), (11 / 2 - 4 / 2, 7 / 2 - 3 / 2));
+        assert_eq!(pos(Position::Left(1)), (1, 7 / 2 - 3 / 2));
+        assert_eq!(pos(Position::Right(1)), (11 - 4 - 1, 7 / 2 - 3 / 2));
+        assert_eq!(pos(Position::Bot(1)), (11 / 2 - 4 / 2, 1));
+        assert_eq!(pos(Position::Top(1)), (11 / 2 - 4 / 2, 7 - 3 - 1));
+        assert_eq!(pos(Position::LeftBot(1, 2)), (1, 2));
+        assert_eq!(pos(Position::LeftTop(1, 2)), (1, 7 - 3 - 2));
+        assert_eq!(pos(Position::RightBot(1, 2)), (11 - 4 - 1, 2));
+        assert_eq!(pos(Position::RightTop(1, 2)), (11 - 4 - 1, 7 - 3 - 2));
+    }
+
+    #[test]
+    fn center_odd_sizes() {
+        for w in 0..16 {
+            for s in 0..=w {
+                let rect = Position::Center.rect(frame(w, w), (s, s));
+                assert_eq!(rect.x, w / 2 - s / 2, "frame {}, size {}", w, s);
+            }
+        }
+    }
+
+    #[test]
+    fn frame_offset() {
+        let rect = Position::RightTop(1, 1).rect(Rect::new((10, 20), (8, 8)), (2, 2));
+        assert_eq!(rect, Rect::new((15, 25), (2, 2)));
+    }
+
+    #[test]
+    fn anchor_and_pivot() {
+        // The child center at the third of the frame
+        let p = Position::anchored((1.0 / 3.0, 0.5), (0.5, 0.5));
+        assert_eq!(p.rect(frame(90, 10), (10, 4)).pos(), Vec2d::new(25, 3));
+
+        // 30 / 3 must not be rounded to 9 because of the float error
+        let p = Position::anchored((1.0 / 3.0, 0.0), (0.0, 0.0));
+        assert_eq!(p.rect(frame(30, 10), (0, 0)).x, 10);
+    }
+
+    #[test]
+    fn rounding() {
+        let p = Position::anchored((0.25, 0.0), (0.0, 0.0));
+
+        assert_eq!(p.rect(frame(10, 0), (0, 0)).x, 2);
+        assert_eq!(p.rect(frame(11, 0), (0, 0)).x, 3);
+        assert_eq!(p.rect(frame(14, 0), (0, 0)).x, 3);
+    }
+
+    #[test]
+    fn percent() {
+        let p = Position::percent(0.25, 1.0);
+        assert_eq!(p.rect(frame(100, 50), (20, 10)).pos(), Vec2d::new(20, 40));
+
+        let p = Position::percent(1.0, 0.0);
+        assert_eq!(p.rect(frame(100, 50), (20, 10)).pos(), Vec2d::new(80, 0));
+    }
+
+    #[test]
+    fn offset_and_pad() {
+        let anchor = Anchor::new((0.0, 0.0), (0.0, 0.0))
+            .offset((0.1, 0.5))
+            .pad((-1, 2));
+
+        let rect = Position::from(anchor).rect(frame(50, 20), (5, 5));
+        assert_eq!(rect.pos(), Vec2d::new(4, 12));
+    }
 }
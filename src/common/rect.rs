@@ -1,4 +1,7 @@
-use super::Vec2d;
+use super::{
+    Insets,
+    Vec2d,
+};
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub struct Rect<T> {
@@ -99,6 +102,43 @@ impl<T> Rect<T>
         self.translate(delta);
         self
     }
+
+    /// Returns the rect with every side moved inwards.
+    pub fn inset<I>(&self, insets: I) -> Self
+        where
+            I: Into<Insets<T>>,
+    {
+        let insets = insets.into();
+
+        Rect {
+            x: self.x + insets.left,
+            y: self.y + insets.bot,
+            width: self.width - insets.horizontal(),
+            height: self.height - insets.vertical(),
+        }
+    }
+
+    /// Returns the rect with every side moved outwards.
+    pub fn outset<I>(&self, insets: I) -> Self
+        where
+            I: Into<Insets<T>>,
+    {
+        let insets = insets.into();
+
+        Rect {
+            x: self.x - insets.left,
+            y: self.y - insets.bot,
+            width: self.width + insets.horizontal(),
+            height: self.height + insets.vertical(),
+        }
+    }
+
+    /// Returns the rect with left and right sides moved inwards by `delta.x`
+    /// and bottom and top sides moved inwards by `delta.y`.
+    pub fn shrink<D>(&self, delta: D) -> Self
+        where
+            D: Into<Vec2d<T>>,
+    { self.inset(Insets::from(delta.into())) }
 }
 
 #[allow(dead_code)]
@@ -106,6 +146,34 @@ impl<T> Rect<T>
     where
         T: Copy + num::Num + PartialOrd,
 {
+    /// Makes the rect between two opposite corners given in any order.
+    pub fn from_corners<A, B>(a: A, b: B) -> Self
+        where
+            A: Into<Vec2d<T>>,
+            B: Into<Vec2d<T>>,
+    {
+        let a = a.into();
+        let b = b.into();
+
+        let (left, right) = (min(a.x, b.x), max(a.x, b.x));
+        let (bot, top) = (min(a.y, b.y), max(a.y, b.y));
+
+        Rect::new((left, bot), (right - left, top - bot))
+    }
+
+    pub fn is_empty(&self) -> bool { self.width <= T::zero() || self.height <= T::zero() }
+
+    /// Returns the area of the rect. The area of an empty rect is zero.
+    pub fn area(&self) -> T {
+        if self.is_empty() {
+            T::zero()
+        } else {
+            self.width * self.height
+        }
+    }
+
+    /// Checks the point is inside the rect. The left and bottom edges are inside,
+    /// but the right and top ones aren't, so adjacent rects never share a point.
     pub fn intersects_point<P>(&self, point: P) -> bool
         where
             P: Into<Vec2d<T>>,
@@ -116,8 +184,231 @@ impl<T> Rect<T>
             && self.bot() <= point.y && point.y < self.top()
     }
 
-    pub fn intersects_rect(&self, rhs: Rect<T>) -> bool {
-        self.left() < rhs.right() && rhs.left() <= self.right()
-            && self.bot() < rhs.top() && rhs.bot() <= self.top()
+    /// Checks the rects have a common area. Rects that only touch don't intersect.
+    pub fn intersects_rect(&self, rhs: Rect<T>) -> bool { self.intersection(rhs).is_some() }
+
+    /// Returns the common area of the rects, or `None` if it is empty.
+    pub fn intersection(&self, rhs: Rect<T>) -> Option<Self> {
+        let left = max(self.left(), rhs.left());
+        let right = min(self.right(), rhs.right());
+        let bot = max(self.bot(), rhs.bot());
+        let top = min(self.top(), rhs.top());
+
+        if left < right && bot < top {
+            Some(Rect::new((left, bot), (right - left, top - bot)))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the smallest rect containing both rects. Empty rects are ignored.
+    pub fn union(&self, rhs: Rect<T>) -> Self {
+        if rhs.is_empty() {
+            return *self;
+        }
+
+        if self.is_empty() {
+            return rhs;
+        }
+
+        Rect::from_corners(
+            (min(self.left(), rhs.left()), min(self.bot(), rhs.bot())),
+            (max(self.right(), rhs.right()), max(self.top(), rhs.top())),
+        )
+    }
+
+    /// Checks the `rhs` lies entirely inside of the rect.
+    pub fn contains_rect(&self, rhs: Rect<T>) -> bool {
+        self.left() <= rhs.left() && rhs.right() <= self.right()
+            && self.bot() <= rhs.bot() && rhs.top() <= self.top()
+    }
+
+    /// Returns the nearest to the `point` position between the rect edges (both inclusive).
+    pub fn clamp_point<P>(&self, point: P) -> Vec2d<T>
+        where
+            P: Into<Vec2d<T>>,
+    {
+        let point = point.into();
+
+        Vec2d::new(
+            max(self.left(), min(point.x, self.right())),
+            max(self.bot(), min(point.y, self.top())),
+        )
+    }
+
+    /// Moves the rect inside of the `frame`. If the rect is larger than the frame,
+    /// it's aligned to the left or bottom edge of the frame.
+    pub fn clamp_into(&self, frame: Rect<T>) -> Self {
+        let x = max(frame.left(), min(self.x, frame.right() - self.width));
+        let y = max(frame.bot(), min(self.y, frame.top() - self.height));
+
+        Rect::new((x, y), self.size())
+    }
+
+    /// Splits the rect by a vertical line at `at` from the left edge.
+    /// Returns the left and right parts. The `at` is clamped to the rect width.
+    pub fn split_horizontal(&self, at: T) -> (Self, Self) {
+        let at = max(T::zero(), min(at, self.width));
+
+        (
+            Rect::new(self.pos(), (at, self.height)),
+            Rect::new((self.x + at, self.y), (self.width - at, self.height)),
+        )
+    }
+
+    /// Splits the rect by a horizontal line at `at` from the bottom edge.
+    /// Returns the bottom and top parts. The `at` is clamped to the rect height.
+    pub fn split_vertical(&self, at: T) -> (Self, Self) {
+        let at = max(T::zero(), min(at, self.height));
+
+        (
+            Rect::new(self.pos(), (self.width, at)),
+            Rect::new((self.x, self.y + at), (self.width, self.height - at)),
+        )
+    }
+}
+
+fn min<T>(a: T, b: T) -> T
+    where
+        T: PartialOrd,
+{ if b < a { b } else { a } }
+
+fn max<T>(a: T, b: T) -> T
+    where
+        T: PartialOrd,
+{ if a < b { b } else { a } }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x: i32, y: i32, w: i32, h: i32) -> Rect<i32> { Rect::new((x, y), (w, h)) }
+
+    #[test]
+    fn from_corners() {
+        assert_eq!(Rect::from_corners((1, 2), (4, 6)), rect(1, 2, 3, 4));
+        assert_eq!(Rect::from_corners((4, 2), (1, 6)), rect(1, 2, 3, 4));
+        assert_eq!(Rect::from_corners((4, 6), (1, 2)), rect(1, 2, 3, 4));
+        assert_eq!(Rect::from_corners((1, 1), (1, 1)), rect(1, 1, 0, 0));
+    }
+
+    #[test]
+    fn is_empty_and_area() {
+        assert!(!rect(0, 0, 2, 3).is_empty());
+        assert!(rect(0, 0, 0, 3).is_empty());
+        assert!(rect(0, 0, 2, -1).is_empty());
+
+        assert_eq!(rect(5, 5, 2, 3).area(), 6);
+        assert_eq!(rect(5, 5, -2, 3).area(), 0);
+        assert_eq!(Rect::new((0.0, 0.0), (0.5, 4.0)).area(), 2.0);
+    }
+
+    #[test]
+    fn intersects_point() {
+        let r = rect(0, 0, 2, 2);
+
+        assert!(r.intersects_point((0, 0)));
+        assert!(r.intersects_point((1, 1)));
+        assert!(!r.intersects_point((2, 1)));
+        assert!(!r.intersects_point((1, 2)));
+        assert!(!r.intersects_point((-1, 0)));
+        assert!(!rect(0, 0, 0, 0).intersects_point((0, 0)));
+    }
+
+    #[test]
+    fn intersects_rect_edges() {
+        let r = rect(0, 0, 2, 2);
+
+        // Touching rects don't intersect from any side
+        assert!(!r.intersects_rect(rect(2, 0, 2, 2)));
+        assert!(!r.intersects_rect(rect(-2, 0, 2, 2)));
+        assert!(!r.intersects_rect(rect(0, 2, 2, 2)));
+        assert!(!r.intersects_rect(rect(0, -2, 2, 2)));
+
+        assert!(r.intersects_rect(rect(1, 1, 2, 2)));
+        assert!(r.intersects_rect(rect(-1, -1, 2, 2)));
+        assert!(r.intersects_rect(rect(-1, -1, 4, 4)));
+
+        // The relation is symmetric
+        assert_eq!(r.intersects_rect(rect(2, 0, 2, 2)), rect(2, 0, 2, 2).intersects_rect(r));
+        assert!(!r.intersects_rect(rect(1, 1, 0, 0)));
+    }
+
+    #[test]
+    fn intersection() {
+        let r = rect(0, 0, 4, 4);
+
+        assert_eq!(r.intersection(rect(2, 1, 4, 2)), Some(rect(2, 1, 2, 2)));
+        assert_eq!(r.intersection(rect(1, 1, 1, 1)), Some(rect(1, 1, 1, 1)));
+        assert_eq!(r.intersection(rect(-2, -2, 10, 10)), Some(r));
+        assert_eq!(r.intersection(rect(4, 0, 1, 1)), None);
+        assert_eq!(r.intersection(rect(5, 5, 1, 1)), None);
+        assert_eq!(r.intersection(rect(1, 1, 0, 2)), None);
+    }
+
+    #[test]
+    fn union() {
+        let r = rect(0, 0, 2, 2);
+
+        assert_eq!(r.union(rect(3, -1, 1, 1)), rect(0, -1, 4, 3));
+        assert_eq!(r.union(rect(1, 1, 0, 0)), r);
+        assert_eq!(rect(9, 9, 0, 0).union(r), r);
+        assert_eq!(r.union(r), r);
+    }
+
+    #[test]
+    fn contains_rect() {
+        let r = rect(0, 0, 4, 4);
+
+        assert!(r.contains_rect(r));
+        assert!(r.contains_rect(rect(1, 1, 2, 2)));
+        assert!(r.contains_rect(rect(2, 2, 2, 2)));
+        assert!(!r.contains_rect(rect(3, 3, 2, 2)));
+        assert!(!r.contains_rect(rect(-1, 0, 2, 2)));
+    }
+
+    #[test]
+    fn clamp_point() {
+        let r = rect(1, 1, 4, 4);
+
+        assert_eq!(r.clamp_point((2, 3)), Vec2d::new(2, 3));
+        assert_eq!(r.clamp_point((-3, 10)), Vec2d::new(1, 5));
+        assert_eq!(r.clamp_point((7, 0)), Vec2d::new(5, 1));
+    }
+
+    #[test]
+    fn clamp_into() {
+        let frame = rect(0, 0, 10, 10);
+
+        assert_eq!(rect(2, 2, 3, 3).clamp_into(frame), rect(2, 2, 3, 3));
+        assert_eq!(rect(8, -2, 3, 3).clamp_into(frame), rect(7, 0, 3, 3));
+        assert_eq!(rect(-5, 9, 3, 3).clamp_into(frame), rect(0, 7, 3, 3));
+        assert_eq!(rect(4, 4, 12, 3).clamp_into(frame), rect(0, 4, 12, 3));
+    }
+
+    #[test]
+    fn split() {
+        let r = rect(1, 2, 10, 6);
+
+        assert_eq!(r.split_horizontal(4), (rect(1, 2, 4, 6), rect(5, 2, 6, 6)));
+        assert_eq!(r.split_horizontal(20), (r, rect(11, 2, 0, 6)));
+        assert_eq!(r.split_horizontal(-1), (rect(1, 2, 0, 6), r));
+
+        assert_eq!(r.split_vertical(2), (rect(1, 2, 10, 2), rect(1, 4, 10, 4)));
+        assert_eq!(r.split_vertical(6), (r, rect(1, 8, 10, 0)));
+    }
+
+    #[test]
+    fn inset_and_outset() {
+        let r = rect(0, 0, 10, 10);
+
+        assert_eq!(r.inset(1), rect(1, 1, 8, 8));
+        assert_eq!(r.inset(Insets::new(1, 2, 3, 4)), rect(1, 3, 7, 3));
+        assert_eq!(r.shrink((2, 1)), rect(2, 1, 6, 8));
+        assert_eq!(r.shrink((6, 0)), rect(6, 0, -2, 10));
+
+        assert_eq!(r.outset(1), rect(-1, -1, 12, 12));
+        assert_eq!(r.outset(Insets::new(1, 2, 3, 4)), rect(-1, -3, 13, 17));
+        assert_eq!(r.inset((1, 2, 3, 4)).outset((1, 2, 3, 4)), r);
     }
 }
@@ -51,6 +51,69 @@ impl<T> Vec2d<T>
         T: Copy + num::Num,
 {
     pub fn half(self) -> Vec2d<T> { self / (T::one() + T::one()) }
+
+    #[allow(dead_code)]
+    pub fn dot(self, rhs: Vec2d<T>) -> T { self.x * rhs.x + self.y * rhs.y }
+}
+
+#[allow(dead_code)]
+impl<T> Vec2d<T>
+    where
+        T: Copy + PartialOrd,
+{
+    /// Component-wise minimum.
+    pub fn min(self, rhs: Vec2d<T>) -> Vec2d<T> {
+        Vec2d::new(
+            if rhs.x < self.x { rhs.x } else { self.x },
+            if rhs.y < self.y { rhs.y } else { self.y },
+        )
+    }
+
+    /// Component-wise maximum.
+    pub fn max(self, rhs: Vec2d<T>) -> Vec2d<T> {
+        Vec2d::new(
+            if self.x < rhs.x { rhs.x } else { self.x },
+            if self.y < rhs.y { rhs.y } else { self.y },
+        )
+    }
+
+    /// Component-wise clamp between `min` and `max`.
+    pub fn clamp(self, min: Vec2d<T>, max: Vec2d<T>) -> Vec2d<T> { self.max(min).min(max) }
+}
+
+#[allow(dead_code)]
+impl<T> Vec2d<T>
+    where
+        T: Copy + num::Signed,
+{
+    pub fn abs(self) -> Vec2d<T> { Vec2d::new(self.x.abs(), self.y.abs()) }
+}
+
+#[allow(dead_code)]
+impl<T> Vec2d<T>
+    where
+        T: Copy + num::Float,
+{
+    pub fn length(self) -> T { self.dot(self).sqrt() }
+
+    /// Returns the vector of the unit length. The zero vector stays zero.
+    pub fn normalize(self) -> Vec2d<T> {
+        let length = self.length();
+
+        if length == T::zero() {
+            self
+        } else {
+            self / length
+        }
+    }
+
+    /// Linear interpolation from `self` (`t` = 0) to `rhs` (`t` = 1).
+    pub fn lerp(self, rhs: Vec2d<T>, t: T) -> Vec2d<T> {
+        Vec2d::new(
+            self.x + (rhs.x - self.x) * t,
+            self.y + (rhs.y - self.y) * t,
+        )
+    }
 }
 
 impl<T> From<[T; 2]> for Vec2d<T> {
@@ -68,6 +131,22 @@ impl<T> From<glm::TVec2<T>> for Vec2d<T>
     fn from(v: glm::TVec2<T>) -> Self { Vec2d::new(v.x, v.y) }
 }
 
+impl<T> From<Vec2d<T>> for glm::TVec2<T>
+    where
+        T: PartialEq + Copy + std::fmt::Debug + 'static,
+{
+    fn from(v: Vec2d<T>) -> Self { glm::TVec2::new(v.x, v.y) }
+}
+
+impl<T> std::ops::Neg for Vec2d<T>
+    where
+        T: std::ops::Neg,
+{
+    type Output = Vec2d<T::Output>;
+
+    fn neg(self) -> Self::Output { Vec2d::new(-self.x, -self.y) }
+}
+
 impl<T, R> std::ops::Add<R> for Vec2d<T>
     where
         R: Into<Vec2d<T>>,
@@ -137,6 +216,24 @@ impl<T> std::ops::Rem<T> for Vec2d<T>
     fn rem(self, rhs: T) -> Self::Output { Vec2d::new(self.x % rhs, self.y % rhs) }
 }
 
+impl<T> std::ops::Mul<Vec2d<T>> for Vec2d<T>
+    where
+        T: std::ops::Mul<T>,
+{
+    type Output = Vec2d<T::Output>;
+
+    fn mul(self, rhs: Vec2d<T>) -> Self::Output { Vec2d::new(self.x * rhs.x, self.y * rhs.y) }
+}
+
+impl<T> std::ops::Div<Vec2d<T>> for Vec2d<T>
+    where
+        T: std::ops::Div<T>,
+{
+    type Output = Vec2d<T::Output>;
+
+    fn div(self, rhs: Vec2d<T>) -> Self::Output { Vec2d::new(self.x / rhs.x, self.y / rhs.y) }
+}
+
 impl<T> std::ops::MulAssign<T> for Vec2d<T>
     where
         T: std::ops::Mul<T, Output=T> + Copy,
@@ -157,3 +254,65 @@ impl<T> std::ops::RemAssign<T> for Vec2d<T>
 {
     fn rem_assign(&mut self, rhs: T) { *self = *self % rhs }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn neg() {
+        assert_eq!(-Vec2d::new(1, -2), Vec2d::new(-1, 2));
+        assert_eq!(-Vec2d::new(0.5, 0.0), Vec2d::new(-0.5, -0.0));
+    }
+
+    #[test]
+    fn component_wise() {
+        assert_eq!(Vec2d::new(2, 3) * Vec2d::new(4, -1), Vec2d::new(8, -3));
+        assert_eq!(Vec2d::new(9, 8) / Vec2d::new(3, 2), Vec2d::new(3, 4));
+        assert_eq!(Vec2d::new(9, 8) * 2, Vec2d::new(18, 16));
+    }
+
+    #[test]
+    fn min_max_clamp() {
+        let a = Vec2d::new(1, 5);
+        let b = Vec2d::new(3, 2);
+
+        assert_eq!(a.min(b), Vec2d::new(1, 2));
+        assert_eq!(a.max(b), Vec2d::new(3, 5));
+        assert_eq!(Vec2d::new(-4, 9).clamp(Vec2d::new(0, 0), Vec2d::new(5, 5)), Vec2d::new(0, 5));
+        assert_eq!(Vec2d::new(2, 3).clamp(Vec2d::new(0, 0), Vec2d::new(5, 5)), Vec2d::new(2, 3));
+    }
+
+    #[test]
+    fn abs_and_dot() {
+        assert_eq!(Vec2d::new(-3, 4).abs(), Vec2d::new(3, 4));
+        assert_eq!(Vec2d::new(1, 2).dot(Vec2d::new(3, -4)), -5);
+    }
+
+    #[test]
+    fn length_and_normalize() {
+        let v = Vec2d::new(3.0_f32, 4.0);
+
+        assert_eq!(v.length(), 5.0);
+        assert_eq!(v.normalize(), Vec2d::new(0.6, 0.8));
+        assert_eq!(Vec2d::new(0.0_f32, 0.0).normalize(), Vec2d::new(0.0, 0.0));
+    }
+
+    #[test]
+    fn lerp() {
+        let a = Vec2d::new(0.0_f32, 10.0);
+        let b = Vec2d::new(10.0, 0.0);
+
+        assert_eq!(a.lerp(b, 0.0), a);
+        assert_eq!(a.lerp(b, 1.0), b);
+        assert_eq!(a.lerp(b, 0.25), Vec2d::new(2.5, 7.5));
+    }
+
+    #[test]
+    fn glm() {
+        let v: glm::TVec2<i32> = Vec2d::new(1, 2).into();
+
+        assert_eq!(v, glm::vec2(1, 2));
+        assert_eq!(Vec2d::from(v), Vec2d::new(1, 2));
+    }
+}
@@ -0,0 +1,243 @@
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+use super::{Texture, TextureError};
+
+#[derive(Debug)]
+#[allow(dead_code)]
+pub enum AssetError {
+    /// The file isn't found in any root.
+    NotFound(PathBuf),
+    TextureError(TextureError),
+}
+
+impl From<TextureError> for AssetError {
+    fn from(e: TextureError) -> Self { AssetError::TextureError(e) }
+}
+
+/// Loads files from asset roots and keeps them, so each file is loaded once.
+///
+/// Textures are shared by `Rc` handles. The cache holds its own handle until the `unload`,
+/// then the texture is freed when the last handle is dropped.
+#[derive(Debug)]
+pub struct Assets<T = Texture> {
+    roots: Vec<PathBuf>,
+    /// Textures by the found file and whether they are sRGB.
+    textures: RefCell<HashMap<(PathBuf, bool), Rc<T>>>,
+}
+
+#[allow(dead_code)]
+impl Assets {
+    /// Creates the cache that looks for files in the `data` directory.
+    pub fn new() -> Self { Assets::with_roots(vec!["data"]) }
+
+    /// Returns the texture of the file, loading it at the first call.
+    /// The sRGB texture is loaded separately, see `Render::is_srgb`.
+    pub fn texture<P>(&self, path: P, srgb: bool) -> Result<Rc<Texture>, AssetError>
+        where
+            P: AsRef<Path>,
+    {
+        self.load(path, srgb, |file| {
+            let file = file.to_string_lossy();
+
+            let texture = if srgb {
+                Texture::from_file_srgb(file)
+            } else {
+                Texture::from_file(file)
+            };
+
+            Ok(texture?)
+        })
+    }
+}
+
+#[allow(dead_code)]
+impl<T> Assets<T> {
+    pub fn with_roots<I, P>(roots: I) -> Self
+        where
+            I: IntoIterator<Item=P>,
+            P: Into<PathBuf>,
+    {
+        Assets {
+            roots: roots.into_iter().map(Into::into).collect(),
+            textures: RefCell::new(HashMap::new()),
+        }
+    }
+
+    pub fn roots(&self) -> &[PathBuf] { &self.roots }
+
+    /// Adds the root that is searched after the others.
+    pub fn add_root<P>(&mut self, root: P)
+        where
+            P: Into<PathBuf>,
+    { self.roots.push(root.into()) }
+
+    /// Finds the file in roots in order. If it isn't there, the path is taken as is.
+    pub fn resolve<P>(&self, path: P) -> Option<PathBuf>
+        where
+            P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+
+        self.roots
+            .iter()
+            .map(|root| root.join(path))
+            .chain(std::iter::once(path.to_path_buf()))
+            .find(|file| file.is_file())
+    }
+
+    /// Returns the cached asset of the file, or loads the found file by the `load`.
+    fn load<P, F>(&self, path: P, srgb: bool, load: F) -> Result<Rc<T>, AssetError>
+        where
+            P: AsRef<Path>,
+            F: FnOnce(&Path) -> Result<T, AssetError>,
+    {
+        let file = match self.resolve(path.as_ref()) {
+            Some(file) => file,
+            None => return Err(AssetError::NotFound(path.as_ref().to_path_buf())),
+        };
+
+        let key = (file, srgb);
+        if let Some(texture) = self.textures.borrow().get(&key) {
+            return Ok(Rc::clone(texture));
+        }
+
+        let texture = Rc::new(load(&key.0)?);
+        self.textures.borrow_mut().insert(key, Rc::clone(&texture));
+
+        Ok(texture)
+    }
+
+    /// Whether the texture of the file is in the cache.
+    pub fn is_loaded<P>(&self, path: P) -> bool
+        where
+            P: AsRef<Path>,
+    {
+        match self.resolve(path) {
+            Some(file) => self.textures.borrow().keys().any(|(loaded, _)| *loaded == file),
+            None => false,
+        }
+    }
+
+    /// Removes textures of the file from the cache. Returns `true` if there were any.
+    /// Handles keep the texture until they are dropped.
+    pub fn unload<P>(&self, path: P) -> bool
+        where
+            P: AsRef<Path>,
+    {
+        let file = match self.resolve(path) {
+            Some(file) => file,
+            None => return false,
+        };
+
+        let mut textures = self.textures.borrow_mut();
+        let len = textures.len();
+        textures.retain(|(loaded, _), _| *loaded != file);
+
+        textures.len() != len
+    }
+
+    /// Removes textures that are used only by the cache.
+    pub fn unload_unused(&self) {
+        self.textures
+            .borrow_mut()
+            .retain(|_, texture| Rc::strong_count(texture) > 1)
+    }
+
+    /// Removes all textures from the cache.
+    pub fn clear(&self) { self.textures.borrow_mut().clear() }
+
+    /// The number of cached textures.
+    pub fn len(&self) -> usize { self.textures.borrow().len() }
+
+    pub fn is_empty(&self) -> bool { self.textures.borrow().is_empty() }
+}
+
+impl Default for Assets {
+    fn default() -> Self { Assets::new() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve() {
+        let mut assets: Assets = Assets::with_roots(vec!["data/font"]);
+
+        assert_eq!(assets.resolve("0.png"), Some(PathBuf::from("data/font/0.png")));
+        assert_eq!(assets.resolve("1.png"), None);
+        assert_eq!(assets.resolve("data/1.png"), Some(PathBuf::from("data/1.png")));
+
+        assets.add_root("data");
+        assert_eq!(assets.resolve("1.png"), Some(PathBuf::from("data/1.png")));
+        assert_eq!(assets.resolve("font"), None);
+        assert!(!assets.is_loaded("1.png"));
+    }
+
+    /// Assets of found files, since textures need the GL context.
+    fn assets() -> Assets<PathBuf> { Assets::with_roots(vec!["data"]) }
+
+    fn load(assets: &Assets<PathBuf>, path: &str) -> Result<Rc<PathBuf>, AssetError> {
+        assets.load(path, false, |file| Ok(file.to_path_buf()))
+    }
+
+    #[test]
+    fn cached() {
+        let assets = assets();
+
+        let first = load(&assets, "1.png").unwrap();
+        assert_eq!(*first, PathBuf::from("data/1.png"));
+
+        // Found in the cache, so the loader isn't called
+        let second = assets.load("1.png", false, |_| panic!("loaded twice")).unwrap();
+        assert!(Rc::ptr_eq(&first, &second));
+
+        let srgb = assets.load("1.png", true, |file| Ok(file.to_path_buf())).unwrap();
+        assert!(!Rc::ptr_eq(&first, &srgb));
+        assert_eq!(assets.len(), 2);
+
+        assert!(matches!(load(&assets, "2.png"), Err(AssetError::NotFound(_))));
+        assert_eq!(assets.len(), 2);
+    }
+
+    #[test]
+    fn unload() {
+        let assets = assets();
+        let first = load(&assets, "1.png").unwrap();
+        load(&assets, "u.png").unwrap();
+        assert!(assets.is_loaded("1.png"));
+
+        assert!(assets.unload("1.png"));
+        assert!(!assets.unload("1.png"));
+        assert!(!assets.is_loaded("1.png"));
+        assert!(assets.is_loaded("u.png"));
+
+        // The handle keeps the asset, but the next call loads it again
+        let second = load(&assets, "1.png").unwrap();
+        assert!(!Rc::ptr_eq(&first, &second));
+
+        assets.clear();
+        assert!(assets.is_empty());
+    }
+
+    #[test]
+    fn unload_unused() {
+        let assets = assets();
+        let used = load(&assets, "1.png").unwrap();
+        load(&assets, "u.png").unwrap();
+
+        assets.unload_unused();
+        assert_eq!(assets.len(), 1);
+        assert!(assets.is_loaded("1.png"));
+        assert!(!assets.is_loaded("u.png"));
+
+        drop(used);
+        assets.unload_unused();
+        assert!(assets.is_empty());
+    }
+}
@@ -0,0 +1,361 @@
+use std::{
+    path::PathBuf,
+    rc::Rc,
+};
+
+use im::{
+    DynamicImage,
+    ImageError,
+    RgbaImage,
+};
+
+use super::{
+    super::common::{
+        Rect,
+        Vec2d,
+    },
+    assets::Assets,
+    texture::{Texture, TextureError},
+};
+
+#[derive(Debug)]
+#[allow(dead_code)]
+pub enum AtlasError {
+    ImageError(ImageError),
+    TextureError(TextureError),
+    /// The file isn't found in any root of assets.
+    NotFound(PathBuf),
+    /// The image with the name doesn't fit the page with the padding.
+    TooLarge(String),
+}
+
+impl From<ImageError> for AtlasError {
+    fn from(e: ImageError) -> Self { AtlasError::ImageError(e) }
+}
+
+impl From<TextureError> for AtlasError {
+    fn from(e: TextureError) -> Self { AtlasError::TextureError(e) }
+}
+
+/// The handle of the image added to the atlas.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct AtlasId(usize);
+
+/// Where the image is placed in the atlas.
+#[derive(Clone, Debug)]
+pub struct AtlasRegion {
+    pub name: String,
+    pub page: usize,
+    /// The rect in pixels of the page, from the top left corner as the image is stored.
+    pub rect: Rect<i32>,
+    /// The rect in texture coordinates, it's passed to `Render::draw_rect_st`.
+    pub st: Rect<f32>,
+}
+
+/// Places rects on shelves, rows of the height of the highest rect in it.
+#[derive(Debug)]
+struct ShelfPacker {
+    size: Vec2d<i32>,
+    /// The top and the height of the shelf, and the filled width.
+    shelves: Vec<(i32, i32, i32)>,
+}
+
+impl ShelfPacker {
+    fn new(size: Vec2d<i32>) -> Self {
+        ShelfPacker {
+            size,
+            shelves: vec![],
+        }
+    }
+
+    /// Returns the position of the rect, or `None` if there is no place.
+    fn place(&mut self, size: Vec2d<i32>) -> Option<Vec2d<i32>> {
+        let width = self.size.x;
+
+        // The shelf with the least wasted height
+        let best = self.shelves
+            .iter_mut()
+            .filter(|(_, height, filled)| *height >= size.y && width - *filled >= size.x)
+            .min_by_key(|(_, height, _)| *height - size.y);
+
+        if let Some((top, _, filled)) = best {
+            let pos = Vec2d::new(*filled, *top);
+            *filled += size.x;
+            return Some(pos);
+        }
+
+        let top = self.shelves.last().map_or(0, |&(top, height, _)| top + height);
+        if top + size.y > self.size.y || size.x > width {
+            return None;
+        }
+
+        self.shelves.push((top, size.y, size.x));
+        Some(Vec2d::new(0, top))
+    }
+}
+
+/// Packs rects of the sizes into pages. Higher rects are placed first, so shelves are tight.
+/// Returns the page and the position of each rect, or the index of the rect that doesn't fit.
+fn pack(sizes: &[Vec2d<i32>], page: Vec2d<i32>) -> Result<Vec<(usize, Vec2d<i32>)>, usize> {
+    let mut order: Vec<usize> = (0..sizes.len()).collect();
+    order.sort_by_key(|&i| (-sizes[i].y, -sizes[i].x));
+
+    let mut pages: Vec<ShelfPacker> = vec![];
+    let mut places = vec![(0, Vec2d::default()); sizes.len()];
+
+    for i in order {
+        let size = sizes[i];
+        if size.x > page.x || size.y > page.y {
+            return Err(i);
+        }
+
+        let place = pages
+            .iter_mut()
+            .enumerate()
+            .find_map(|(n, packer)| packer.place(size).map(|pos| (n, pos)));
+
+        places[i] = match place {
+            Some(place) => place,
+            None => {
+                let mut packer = ShelfPacker::new(page);
+                let pos = packer.place(size).ok_or(i)?;
+                pages.push(packer);
+                (pages.len() - 1, pos)
+            }
+        };
+    }
+
+    Ok(places)
+}
+
+/// Copies the image into the page with the padding around it.
+/// With the `extrude` the padding repeats edge pixels, otherwise it's transparent.
+fn blit(page: &mut RgbaImage, img: &RgbaImage, pos: Vec2d<i32>, padding: i32, extrude: bool) {
+    let (w, h) = (img.width() as i32, img.height() as i32);
+    let border = if extrude { padding } else { 0 };
+
+    if w == 0 || h == 0 {
+        return;
+    }
+
+    for y in -border..h + border {
+        for x in -border..w + border {
+            let pixel = *img.get_pixel(x.clamp(0, w - 1) as u32, y.clamp(0, h - 1) as u32);
+            page.put_pixel((pos.x + padding + x) as u32, (pos.y + padding + y) as u32, pixel);
+        }
+    }
+}
+
+/// Collects images to pack them into the `TextureAtlas`.
+#[derive(Debug)]
+pub struct AtlasBuilder {
+    page: Vec2d<i32>,
+    padding: i32,
+    extrude: bool,
+    srgb: bool,
+    images: Vec<(String, RgbaImage)>,
+}
+
+#[allow(dead_code)]
+impl AtlasBuilder {
+    /// Sets the space around each image, so neighbours don't bleed into it. It's 1 by default.
+    pub fn padding(mut self, padding: i32) -> Self {
+        self.padding = padding.max(0);
+        self
+    }
+
+    /// Fills the padding with edge pixels of images, so sampling at edges doesn't catch
+    /// the transparent border. It's enabled by default.
+    pub fn extrude(mut self, extrude: bool) -> Self {
+        self.extrude = extrude;
+        self
+    }
+
+    /// Creates sRGB textures, as `Render::is_srgb` requires.
+    pub fn srgb(mut self, srgb: bool) -> Self {
+        self.srgb = srgb;
+        self
+    }
+
+    /// Adds the image from the file found in roots of the `assets`, as `Image::new` does.
+    /// The file is the name of the region.
+    pub fn add_file<S>(&mut self, file: S, assets: &Assets) -> Result<AtlasId, AtlasError>
+        where
+            S: Into<String>,
+    {
+        let file = file.into();
+        let path = match assets.resolve(file.as_str()) {
+            Some(path) => path,
+            None => return Err(AtlasError::NotFound(file.into())),
+        };
+
+        let img = im::open(path)?;
+        Ok(self.add_image(file, &img))
+    }
+
+    pub fn add_image<S>(&mut self, name: S, img: &DynamicImage) -> AtlasId
+        where
+            S: Into<String>,
+    {
+        self.images.push((name.into(), img.to_rgba8()));
+        AtlasId(self.images.len() - 1)
+    }
+
+    /// Packs images and creates textures of pages.
+    pub fn build(self) -> Result<TextureAtlas, AtlasError> {
+        let (padding, extrude, srgb) = (self.padding, self.extrude, self.srgb);
+        let sizes: Vec<Vec2d<i32>> = self.images
+            .iter()
+            .map(|(_, img)| {
+                let (w, h) = img.dimensions();
+                Vec2d::new(w as i32 + padding * 2, h as i32 + padding * 2)
+            })
+            .collect();
+
+        let places = match pack(&sizes, self.page) {
+            Ok(places) => places,
+            Err(i) => return Err(AtlasError::TooLarge(self.images[i].0.clone())),
+        };
+
+        let page_count = places.iter().map(|&(page, _)| page + 1).max().unwrap_or(0);
+        let mut pages = vec![RgbaImage::new(self.page.x as u32, self.page.y as u32); page_count];
+        let page_size = self.page.cast::<f32>();
+
+        let regions = self.images
+            .into_iter()
+            .zip(places)
+            .map(|((name, img), (page, pos))| {
+                blit(&mut pages[page], &img, pos, padding, extrude);
+
+                let (w, h) = img.dimensions();
+                let rect = Rect::new(pos + Vec2d::new(padding, padding), (w as i32, h as i32));
+                let st = Rect::new(
+                    (rect.x as f32 / page_size.x, rect.y as f32 / page_size.y),
+                    (rect.width as f32 / page_size.x, rect.height as f32 / page_size.y),
+                );
+
+                AtlasRegion { name, page, rect, st }
+            })
+            .collect();
+
+        let pages = pages
+            .into_iter()
+            .map(|page| {
+                let page = DynamicImage::ImageRgba8(page);
+                let texture = if srgb {
+                    Texture::from_image_srgb(&page)
+                } else {
+                    Texture::from_image(&page)
+                };
+
+                texture.map(Rc::new)
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(TextureAtlas { pages, regions })
+    }
+}
+
+/// Many images packed into a few large textures, so they are drawn without switching textures.
+#[derive(Debug)]
+pub struct TextureAtlas {
+    pages: Vec<Rc<Texture>>,
+    regions: Vec<AtlasRegion>,
+}
+
+#[allow(dead_code)]
+impl TextureAtlas {
+    /// Starts the atlas with pages of the size in pixels.
+    pub fn builder<S>(page: S) -> AtlasBuilder
+        where
+            S: Into<Vec2d<i32>>,
+    {
+        AtlasBuilder {
+            page: page.into(),
+            padding: 1,
+            extrude: true,
+            srgb: false,
+            images: vec![],
+        }
+    }
+
+    pub fn region(&self, id: AtlasId) -> &AtlasRegion { &self.regions[id.0] }
+
+    /// Finds the region by the name of the image.
+    pub fn find(&self, name: &str) -> Option<AtlasId> {
+        self.regions.iter().position(|region| region.name == name).map(AtlasId)
+    }
+
+    pub fn page(&self, page: usize) -> &Rc<Texture> { &self.pages[page] }
+
+    /// The texture with the image.
+    pub fn texture(&self, id: AtlasId) -> &Rc<Texture> { self.page(self.region(id).page) }
+
+    pub fn pages(&self) -> usize { self.pages.len() }
+
+    pub fn len(&self) -> usize { self.regions.len() }
+
+    pub fn is_empty(&self) -> bool { self.regions.is_empty() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn packing() {
+        let sizes: Vec<Vec2d<i32>> = [(4, 4), (8, 2), (2, 4), (4, 2), (8, 8)]
+            .iter()
+            .map(|&size| size.into())
+            .collect();
+
+        let places = pack(&sizes, Vec2d::new(8, 8)).unwrap();
+
+        // The largest one takes the whole page
+        assert_eq!(places[4], (0, Vec2d::new(0, 0)));
+        assert_eq!(places[0], (1, Vec2d::new(0, 0)));
+        assert_eq!(places[2], (1, Vec2d::new(4, 0)));
+        assert_eq!(places[1], (1, Vec2d::new(0, 4)));
+        assert_eq!(places[3], (1, Vec2d::new(0, 6)));
+
+        for (i, &(page, pos)) in places.iter().enumerate() {
+            let rect = Rect::new(pos, sizes[i]);
+            assert!(Rect::new((0, 0), (8, 8)).contains_rect(rect));
+
+            for (j, &(other_page, other)) in places.iter().enumerate().skip(i + 1) {
+                let overlaps = Rect::new(other, sizes[j]).intersects_rect(rect);
+                assert!(page != other_page || !overlaps);
+            }
+        }
+
+        assert_eq!(pack(&[Vec2d::new(2, 2), Vec2d::new(9, 1)], Vec2d::new(8, 8)), Err(1));
+    }
+
+    #[test]
+    fn add_file() {
+        let assets = Assets::with_roots(vec!["data"]);
+        let mut builder = TextureAtlas::builder((64, 64));
+
+        assert_eq!(builder.add_file("u.png", &assets).unwrap(), AtlasId(0));
+        assert_eq!(builder.images[0].0, "u.png");
+        assert!(matches!(builder.add_file("data/u.png", &assets), Ok(AtlasId(1))));
+        assert!(matches!(builder.add_file("2.png", &assets), Err(AtlasError::NotFound(_))));
+    }
+
+    #[test]
+    fn extrusion() {
+        let mut img = RgbaImage::new(2, 1);
+        img.put_pixel(0, 0, im::Rgba([255, 0, 0, 255]));
+        img.put_pixel(1, 0, im::Rgba([0, 255, 0, 255]));
+
+        let mut page = RgbaImage::new(4, 3);
+        blit(&mut page, &img, Vec2d::new(0, 0), 1, true);
+        assert_eq!(page.get_pixel(0, 0), &im::Rgba([255, 0, 0, 255]));
+        assert_eq!(page.get_pixel(3, 2), &im::Rgba([0, 255, 0, 255]));
+
+        let mut page = RgbaImage::new(4, 3);
+        blit(&mut page, &img, Vec2d::new(0, 0), 1, false);
+        assert_eq!(page.get_pixel(0, 0), &im::Rgba([0, 0, 0, 0]));
+        assert_eq!(page.get_pixel(1, 1), &im::Rgba([255, 0, 0, 255]));
+    }
+}
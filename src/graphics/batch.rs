@@ -0,0 +1,336 @@
+use std::rc::Rc;
+
+use super::{
+    super::common::{
+        Color,
+        Rect,
+    },
+    shader_data::UsedShader,
+    texture::Texture,
+};
+
+/// Quads of the batch are drawn by one call, so they share the shader and the texture.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct BatchState {
+    pub shader: UsedShader,
+    pub texture: Option<u32>,
+}
+
+impl BatchState {
+    pub fn new(shader: UsedShader, texture: Option<&Texture>) -> Self {
+        BatchState { shader, texture: texture.map(Texture::id) }
+    }
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct Vertex {
+    pos: [f32; 2],
+    st: [f32; 2],
+    col: [f32; 4],
+}
+
+/// Makes vertices of the quad from the top left corner counterclockwise.
+fn quad(rect: Rect<f32>, st: Option<Rect<f32>>, flip_v: bool, col: Color) -> [Vertex; 4] {
+    let st = st.unwrap_or(Rect::new((0.0, 0.0), (1.0, 1.0)));
+    let col = [col.r(), col.g(), col.b(), col.a()];
+
+    let (top, bot) = if flip_v {
+        (st.bot(), st.top())
+    } else {
+        (st.top(), st.bot())
+    };
+
+    let vertex = |x, y, s, t| Vertex { pos: [x, y], st: [s, t], col };
+
+    [
+        vertex(rect.left(), rect.top(), st.left(), top),
+        vertex(rect.left(), rect.bot(), st.left(), bot),
+        vertex(rect.right(), rect.bot(), st.right(), bot),
+        vertex(rect.right(), rect.top(), st.right(), top),
+    ]
+}
+
+/// Quads waiting for the draw call and the number of calls made.
+#[derive(Debug)]
+struct Quads {
+    vertices: Vec<Vertex>,
+    state: Option<BatchState>,
+    draw_calls: usize,
+}
+
+impl Quads {
+    /// The number of quads in the buffer.
+    const CAPACITY: usize = 4096;
+
+    fn new() -> Self {
+        Quads {
+            vertices: Vec::with_capacity(4 * Quads::CAPACITY),
+            state: None,
+            draw_calls: 0,
+        }
+    }
+
+    fn accepts(&self, state: BatchState) -> bool {
+        (self.state.is_none() || self.state == Some(state))
+            && self.vertices.len() < 4 * Quads::CAPACITY
+    }
+
+    fn push(&mut self, state: BatchState, quad: [Vertex; 4]) {
+        debug_assert!(self.accepts(state));
+
+        self.state = Some(state);
+        self.vertices.extend_from_slice(&quad);
+    }
+
+    /// Passes accumulated quads to the `draw` and counts the call. Does nothing without quads.
+    fn flush<F>(&mut self, draw: F)
+        where
+            F: FnOnce(&[Vertex]),
+    {
+        if self.vertices.is_empty() {
+            return;
+        }
+
+        draw(&self.vertices);
+
+        self.vertices.clear();
+        self.state = None;
+        self.draw_calls += 1;
+    }
+}
+
+/// Accumulates quads into the streaming vertex buffer and draws them at once.
+///
+/// The owner flushes the batch when the state of the next quad differs, or before
+/// changing anything that affects drawing, like uniforms, the clip or the framebuffer.
+/// The batch holds the texture of its quads until the flush, so it can't be freed before.
+#[derive(Debug)]
+pub struct QuadBatch {
+    vao: u32,
+    vbo: u32,
+    ebo: u32,
+    quads: Quads,
+    texture: Option<Rc<Texture>>,
+}
+
+impl QuadBatch {
+    const CAPACITY: usize = Quads::CAPACITY;
+
+    pub fn new(pos_index: u32, st_index: u32, col_index: u32) -> Self {
+        let (mut vao, mut vbo, mut ebo) = (0, 0, 0);
+
+        // Two triangles of each quad
+        let indices: Vec<u32> = (0..QuadBatch::CAPACITY as u32)
+            .flat_map(|quad| [0, 1, 2, 0, 2, 3].iter().map(move |i| quad * 4 + i))
+            .collect();
+
+        let stride = std::mem::size_of::<Vertex>() as i32;
+        let offset = |floats: usize| {
+            (floats * std::mem::size_of::<f32>()) as *const std::ffi::c_void
+        };
+
+        unsafe {
+            gl::GenVertexArrays(1, &mut vao);
+            gl::GenBuffers(1, &mut vbo);
+            gl::GenBuffers(1, &mut ebo);
+
+            gl::BindVertexArray(vao);
+            gl::BindBuffer(gl::ARRAY_BUFFER, vbo);
+
+            gl::BufferData(
+                gl::ARRAY_BUFFER,
+                (std::mem::size_of::<Vertex>() * 4 * QuadBatch::CAPACITY) as isize,
+                std::ptr::null(),
+                gl::STREAM_DRAW,
+            );
+
+            gl::BindBuffer(gl::ELEMENT_ARRAY_BUFFER, ebo);
+            gl::BufferData(
+                gl::ELEMENT_ARRAY_BUFFER,
+                (std::mem::size_of::<u32>() * indices.len()) as isize,
+                indices.as_ptr() as *const std::ffi::c_void,
+                gl::STATIC_DRAW,
+            );
+
+            gl::EnableVertexAttribArray(pos_index);
+            gl::VertexAttribPointer(pos_index, 2, gl::FLOAT, gl::FALSE, stride, offset(0));
+
+            gl::EnableVertexAttribArray(st_index);
+            gl::VertexAttribPointer(st_index, 2, gl::FLOAT, gl::FALSE, stride, offset(2));
+
+            gl::EnableVertexAttribArray(col_index);
+            gl::VertexAttribPointer(col_index, 4, gl::FLOAT, gl::FALSE, stride, offset(4));
+
+            gl::BindVertexArray(0);
+            gl::BindBuffer(gl::ARRAY_BUFFER, 0);
+            gl::BindBuffer(gl::ELEMENT_ARRAY_BUFFER, 0);
+        };
+
+        QuadBatch {
+            vao,
+            vbo,
+            ebo,
+            quads: Quads::new(),
+            texture: None,
+        }
+    }
+
+    /// The state of accumulated quads, or `None` if there are no quads.
+    pub fn state(&self) -> Option<BatchState> { self.quads.state }
+
+    /// Whether the quad of the state can be added without the flush.
+    pub fn accepts(&self, state: BatchState) -> bool { self.quads.accepts(state) }
+
+    /// Adds the quad drawn with the texture, which is kept until the flush.
+    pub fn push(
+        &mut self,
+        shader: UsedShader,
+        texture: Option<&Rc<Texture>>,
+        rect: Rect<f32>,
+        st: Option<Rect<f32>>,
+        flip_v: bool,
+        col: Color,
+    ) {
+        let state = BatchState::new(shader, texture.map(Rc::as_ref));
+        self.quads.push(state, quad(rect, st, flip_v, col));
+        self.texture = texture.cloned();
+    }
+
+    /// Draws accumulated quads. The shader, its uniforms and the texture must be already set.
+    pub fn flush(&mut self) {
+        let (vao, vbo) = (self.vao, self.vbo);
+
+        self.quads.flush(|vertices| unsafe {
+            gl::BindVertexArray(vao);
+            gl::BindBuffer(gl::ARRAY_BUFFER, vbo);
+
+            // Orphans the old storage, so the driver doesn't wait for the previous draw
+            gl::BufferData(
+                gl::ARRAY_BUFFER,
+                (std::mem::size_of::<Vertex>() * 4 * QuadBatch::CAPACITY) as isize,
+                std::ptr::null(),
+                gl::STREAM_DRAW,
+            );
+
+            gl::BufferSubData(
+                gl::ARRAY_BUFFER,
+                0,
+                std::mem::size_of_val(vertices) as isize,
+                vertices.as_ptr() as *const std::ffi::c_void,
+            );
+
+            let indices = vertices.len() / 4 * 6;
+            gl::DrawElements(gl::TRIANGLES, indices as i32, gl::UNSIGNED_INT, std::ptr::null());
+        });
+
+        self.texture = None;
+    }
+
+    /// Returns the number of draw calls since the previous call.
+    pub fn take_draw_calls(&mut self) -> usize { std::mem::take(&mut self.quads.draw_calls) }
+}
+
+impl Drop for QuadBatch {
+    fn drop(&mut self) {
+        unsafe {
+            gl::DeleteVertexArrays(1, &self.vao);
+            gl::DeleteBuffers(1, &self.vbo);
+            gl::DeleteBuffers(1, &self.ebo);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quads() {
+        let rect = Rect::new((1.0, 2.0), (3.0, 4.0));
+        let st = Rect::new((0.0, 0.0), (0.5, 0.25));
+        let red = Color::rgb(1.0, 0.0, 0.0);
+
+        let vertices = quad(rect, Some(st), false, red);
+        assert_eq!(vertices[0].pos, [1.0, 6.0]);
+        assert_eq!(vertices[2].pos, [4.0, 2.0]);
+        assert_eq!(vertices[0].st, [0.0, 0.25]);
+        assert_eq!(vertices[2].st, [0.5, 0.0]);
+        assert!(vertices.iter().all(|v| v.col == [1.0, 0.0, 0.0, 1.0]));
+
+        let flipped = quad(rect, Some(st), true, red);
+        assert_eq!(flipped[0].st, [0.0, 0.0]);
+        assert_eq!(flipped[2].st, [0.5, 0.25]);
+    }
+
+    /// Draws quads of states like `Render`, flushing when the next one isn't accepted.
+    /// `None` stands for a clip or a transform change, which flushes the batch.
+    fn draw(quads: &mut Quads, states: &[Option<BatchState>]) -> Vec<(BatchState, usize)> {
+        let rect = quad(Rect::new((0.0, 0.0), (1.0, 1.0)), None, false, Color::white());
+        let mut calls = vec![];
+
+        for &state in states {
+            let current = quads.state;
+            let mut flush = |quads: &mut Quads| {
+                quads.flush(|vertices| calls.push((current.unwrap(), vertices.len() / 4)))
+            };
+
+            match state {
+                Some(state) => {
+                    if !quads.accepts(state) {
+                        flush(quads);
+                    }
+                    quads.push(state, rect);
+                }
+                None => flush(quads),
+            }
+        }
+
+        calls
+    }
+
+    #[test]
+    fn flushes() {
+        let base = |texture| Some(BatchState { shader: UsedShader::Base, texture });
+        let font = |texture| Some(BatchState { shader: UsedShader::Font, texture });
+
+        let mut quads = Quads::new();
+        let calls = draw(&mut quads, &[
+            base(None),
+            base(None),
+            // The texture change
+            base(Some(1)),
+            base(Some(2)),
+            base(Some(2)),
+            // The shader change
+            font(Some(2)),
+            // The clip change, then the one without quads
+            None,
+            None,
+            font(Some(2)),
+            None,
+        ]);
+
+        assert_eq!(calls, [
+            (base(None).unwrap(), 2),
+            (base(Some(1)).unwrap(), 1),
+            (base(Some(2)).unwrap(), 2),
+            (font(Some(2)).unwrap(), 1),
+            (font(Some(2)).unwrap(), 1),
+        ]);
+        assert_eq!(quads.draw_calls, 5);
+        assert_eq!(quads.state, None);
+    }
+
+    #[test]
+    fn capacity() {
+        let state = BatchState { shader: UsedShader::Base, texture: None };
+
+        let mut quads = Quads::new();
+        let calls = draw(&mut quads, &vec![Some(state); Quads::CAPACITY + 1]);
+
+        assert_eq!(calls, [(state, Quads::CAPACITY)]);
+        assert_eq!(quads.vertices.len(), 4);
+        assert_eq!(quads.draw_calls, 1);
+    }
+}
@@ -6,6 +6,8 @@ use super::{
         Vec2d,
     },
     Render,
+    Metrics,
+    HitTest,
 };
 
 #[derive(Copy, Clone, Debug)]
@@ -57,6 +59,15 @@ pub struct DrawParameters {
 }
 
 impl DrawParameters {
+    pub fn new(frame: Rect<i32>) -> Self {
+        DrawParameters {
+            color: Color::white(),
+            position: Position::default(),
+            frame,
+            font_style: FontStyle::default(),
+        }
+    }
+
     pub fn render_rect<S>(&self, size: S) -> Rect<i32>
         where
             S: Into<Vec2d<i32>>,
@@ -65,6 +76,15 @@ impl DrawParameters {
 
 pub trait Draw {
     fn draw(&self, render: &mut Render, params: DrawParameters);
+
+    /// Returns the size the widget will occupy when drawn with the same `params`.
+    fn measure(&self, metrics: &Metrics, params: &DrawParameters) -> Vec2d<i32>;
+
+    /// Records the rect the widget occupies. Containers pass the test to their children.
+    fn hit(&self, hits: &mut HitTest, params: DrawParameters) {
+        let size = self.measure(hits.metrics(), &params);
+        hits.record(params.render_rect(size));
+    }
 }
 
 impl<T> Draw for &T
@@ -72,6 +92,11 @@ impl<T> Draw for &T
         T: Draw,
 {
     fn draw(&self, render: &mut Render, params: DrawParameters) { (**self).draw(render, params) }
+
+    fn measure(&self, metrics: &Metrics, params: &DrawParameters) -> Vec2d<i32> {
+        (**self).measure(metrics, params)
+    }
+    fn hit(&self, hits: &mut HitTest, params: DrawParameters) { (**self).hit(hits, params) }
 }
 
 impl<T> Draw for &mut T
@@ -79,6 +104,11 @@ impl<T> Draw for &mut T
         T: Draw,
 {
     fn draw(&self, render: &mut Render, params: DrawParameters) { (**self).draw(render, params) }
+
+    fn measure(&self, metrics: &Metrics, params: &DrawParameters) -> Vec2d<i32> {
+        (**self).measure(metrics, params)
+    }
+    fn hit(&self, hits: &mut HitTest, params: DrawParameters) { (**self).hit(hits, params) }
 }
 
 impl<T> Draw for Box<T>
@@ -86,6 +116,11 @@ impl<T> Draw for Box<T>
         T: Draw,
 {
     fn draw(&self, render: &mut Render, params: DrawParameters) { (**self).draw(render, params) }
+
+    fn measure(&self, metrics: &Metrics, params: &DrawParameters) -> Vec2d<i32> {
+        (**self).measure(metrics, params)
+    }
+    fn hit(&self, hits: &mut HitTest, params: DrawParameters) { (**self).hit(hits, params) }
 }
 
 impl<T> Draw for Rect<T>
@@ -95,7 +130,19 @@ impl<T> Draw for Rect<T>
     fn draw(&self, render: &mut Render, params: DrawParameters) {
         render.set_color(params.color);
         render.unset_texture();
-        render.draw_rect(self.cast());
+        render.draw_rect(self.cast::<f32>().translated(params.frame.pos().cast::<f32>()));
+    }
+
+    /// The rect is drawn at its own position relative to the frame,
+    /// so it occupies everything up to its top right corner.
+    fn measure(&self, _: &Metrics, _: &DrawParameters) -> Vec2d<i32> {
+        let rect: Rect<i32> = self.cast();
+        Vec2d::new(rect.right(), rect.top())
+    }
+
+    fn hit(&self, hits: &mut HitTest, params: DrawParameters) {
+        let rect: Rect<i32> = self.cast();
+        hits.record(rect.translated(params.frame.pos()));
     }
 }
 
@@ -104,10 +151,20 @@ impl Draw for &str {
         render.set_color(params.color);
         render.print(self, &params);
     }
+
+    fn measure(&self, metrics: &Metrics, params: &DrawParameters) -> Vec2d<i32> {
+        metrics.text_size(self, &params.font_style)
+    }
 }
 
 impl Draw for String {
     fn draw(&self, render: &mut Render, params: DrawParameters) {
         self.as_str().draw(render, params);
     }
+
+    fn measure(&self, metrics: &Metrics, params: &DrawParameters) -> Vec2d<i32> {
+        self.as_str().measure(metrics, params)
+    }
+
+    fn hit(&self, hits: &mut HitTest, params: DrawParameters) { self.as_str().hit(hits, params) }
 }
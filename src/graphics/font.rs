@@ -1,10 +1,11 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, rc::Rc};
 
 use super::{
     super::common::{
         Vec2d,
         Rect,
     },
+    assets::{Assets, AssetError},
     glyphs::*,
     pages::Pages,
     Texture,
@@ -20,58 +21,46 @@ impl GlyphSize {
     pub fn new(left_offset: i32, width: i32) -> Self { GlyphSize { left_offset, width } }
 }
 
-#[derive(Debug)]
-pub struct Font {
-    atlas_size: Vec2d<i32>,
-    glyphs_on_page: i32,
+/// Layout data of the font. It doesn't hold any texture, so text can be measured without GL.
+#[derive(Clone, Debug)]
+pub struct FontMetrics {
     default_size: Vec2d<i32>,
-    default_st: Vec2d<f32>,
     indent: i32,
     line_spacing: i32,
-    pages: Pages<Texture>,
     glyph_widths: HashMap<char, GlyphSize>,
 }
 
-impl Font {
+#[allow(dead_code)]
+impl FontMetrics {
     pub fn new<S>(
-        atlas_size: S,
+        default_size: S,
         indent: i32,
         line_spacing: i32,
-        pages: Pages<Texture>,
         glyph_widths: HashMap<char, GlyphSize>,
     ) -> Self
         where
             S: Into<Vec2d<i32>>,
     {
-        let atlas_size = atlas_size.into();
-
-        let (width, height) = pages.first().size().into_inner();
-        let glyph_width = width / atlas_size.width();
-        let glyph_height = height / atlas_size.height();
-
-        Font {
-            atlas_size,
-            glyphs_on_page: atlas_size.width() * atlas_size.height(),
-            default_size: Vec2d::new(glyph_width, glyph_height),
-            default_st: Vec2d::new(
-                1.0 / atlas_size.width() as f32,
-                1.0 / atlas_size.height() as f32,
-            ),
+        FontMetrics {
+            default_size: default_size.into(),
             indent,
             line_spacing,
-            pages,
             glyph_widths,
         }
     }
 
-    pub fn glyphs(&self, text: &str, mut buf: Vec<Char>, monospaced: bool) -> Glyphs {
+    /// Lays out the text and passes every char to the `f`. Returns the size of the text.
+    fn layout<F>(&self, text: &str, monospaced: bool, mut f: F) -> Vec2d<i32>
+        where
+            F: FnMut(Char),
+    {
         let default = GlyphSize::new(0, self.default_size.width());
         let mut width = 0;
         let mut size = Vec2d::new(0, self.default_size.height());
 
         for ch in text.chars() {
             if ch == '\n' {
-                buf.push(Char::NewLine);
+                f(Char::NewLine);
 
                 size.x = width.max(size.x);
                 width = 0;
@@ -86,26 +75,110 @@ impl Font {
                         .unwrap_or(default)
                 };
 
-                buf.push(Char::Print(Glyph::new(glyph_size, width, ch as u32)));
+                f(Char::Print(Glyph::new(glyph_size, width, ch as u32)));
 
                 width += glyph_size.width + self.indent;
                 size.x = width.max(size.x);
             }
         }
 
+        size
+    }
+
+    pub fn glyphs(&self, text: &str, mut buf: Vec<Char>, monospaced: bool) -> Glyphs {
+        let size = self.layout(text, monospaced, |ch| buf.push(ch));
         Glyphs::new(buf, size)
     }
 
+    pub fn text_size(&self, text: &str, monospaced: bool) -> Vec2d<i32> {
+        self.layout(text, monospaced, |_| ())
+    }
+
+    /// Returns caret positions before every char and after the last one.
+    /// The `x` is in pixels from the line start and the `y` is the line number from the top.
+    pub fn carets(&self, text: &str, monospaced: bool) -> Vec<Vec2d<i32>> {
+        let glyphs = self.glyphs(text, vec![], monospaced);
+
+        let mut carets = Vec::with_capacity(glyphs.len() + 1);
+        let mut pos = Vec2d::new(0, 0);
+
+        for ch in glyphs.iter() {
+            carets.push(match ch {
+                Char::Print(glyph) => {
+                    pos.x = glyph.delta_x + glyph.size.width + self.indent;
+                    Vec2d::new(glyph.delta_x, pos.y)
+                }
+                Char::NewLine => {
+                    let caret = pos;
+                    pos = Vec2d::new(0, pos.y + 1);
+                    caret
+                }
+            });
+        }
+
+        carets.push(pos);
+        carets
+    }
+
+    pub fn new_line_height(&self) -> i32 { self.line_spacing + self.default_size.height() }
+
+    pub fn default_size(&self) -> Vec2d<i32> { self.default_size }
+
+    pub fn indent(&self) -> i32 { self.indent }
+}
+
+#[derive(Debug)]
+pub struct Font {
+    atlas_size: Vec2d<i32>,
+    glyphs_on_page: i32,
+    default_st: Vec2d<f32>,
+    pages: Pages<Rc<Texture>>,
+    metrics: FontMetrics,
+}
+
+impl Font {
+    pub fn new<S>(
+        atlas_size: S,
+        indent: i32,
+        line_spacing: i32,
+        pages: Pages<Rc<Texture>>,
+        glyph_widths: HashMap<char, GlyphSize>,
+    ) -> Self
+        where
+            S: Into<Vec2d<i32>>,
+    {
+        let atlas_size = atlas_size.into();
+
+        let (width, height) = pages.first().size().into_inner();
+        let glyph_width = width / atlas_size.width();
+        let glyph_height = height / atlas_size.height();
+
+        Font {
+            atlas_size,
+            glyphs_on_page: atlas_size.width() * atlas_size.height(),
+            default_st: Vec2d::new(
+                1.0 / atlas_size.width() as f32,
+                1.0 / atlas_size.height() as f32,
+            ),
+            pages,
+            metrics: FontMetrics::new((glyph_width, glyph_height), indent, line_spacing, glyph_widths),
+        }
+    }
+
+    pub fn glyphs(&self, text: &str, buf: Vec<Char>, monospaced: bool) -> Glyphs {
+        self.metrics.glyphs(text, buf, monospaced)
+    }
+
     pub fn placing(&self, glyph: Glyph, pos: Vec2d<i32>) -> Rect<i32> {
         Rect::new(
             (pos.x + glyph.delta_x, pos.y),
-            (glyph.size.width, self.default_size.height()),
+            (glyph.size.width, self.metrics.default_size.height()),
         )
     }
 
     pub fn st_map(&self, glyph: Glyph) -> Rect<f32> {
         let code_at_page = glyph.code as i32 % self.glyphs_on_page;
-        let default_width = self.default_size.width() as f32;
+        let default_width = self.metrics.default_size.width() as f32;
 
         let left_offset = glyph.size.left_offset as f32 / default_width;
 
@@ -119,20 +192,23 @@ impl Font {
         ))
     }
 
-    pub fn page(&self, code: u32) -> Option<&Texture> {
+    pub fn page(&self, code: u32) -> Option<&Rc<Texture>> {
         let page_code = code as i32 / self.glyphs_on_page;
         self.pages.get(page_code as usize)
     }
 
-    pub fn new_line_height(&self) -> i32 { self.line_spacing + self.default_size.height() }
+    pub fn new_line_height(&self) -> i32 { self.metrics.new_line_height() }
 
-    pub fn default_size(&self) -> Vec2d<i32> { self.default_size }
+    pub fn default_size(&self) -> Vec2d<i32> { self.metrics.default_size() }
+
+    pub fn metrics(&self) -> &FontMetrics { &self.metrics }
 }
 
-impl Default for Font {
-    fn default() -> Self {
-        let p0 = Texture::from_file("./data/font/0.png").unwrap();
-        let p4 = Texture::from_file("./data/font/4.png").unwrap();
+impl Font {
+    /// Loads the default font from `font` files of assets.
+    pub fn load(assets: &Assets) -> Result<Self, AssetError> {
+        let p0 = assets.texture("font/0.png", false)?;
+        let p4 = assets.texture("font/4.png", false)?;
 
         let mut pages = Pages::new(p0);
         pages.add(p4, 4);
@@ -160,6 +236,50 @@ impl Default for Font {
         glyph_widths.insert('Ю', GlyphSize::new(0, 9));
         glyph_widths.insert('ю', GlyphSize::new(0, 9));
 
-        Font::new((16, 16), 0, 1, pages, glyph_widths)
+        Ok(Font::new((16, 16), 0, 1, pages, glyph_widths))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_size() {
+        let mut glyph_widths = HashMap::new();
+        glyph_widths.insert('i', GlyphSize::new(0, 2));
+        let metrics = FontMetrics::new((8, 8), 1, 1, glyph_widths);
+
+        assert_eq!(metrics.text_size("", false), Vec2d::new(0, 8));
+        assert_eq!(metrics.text_size("ab", false), Vec2d::new(18, 8));
+        assert_eq!(metrics.text_size("ii", false), Vec2d::new(6, 8));
+        assert_eq!(metrics.text_size("ii", true), Vec2d::new(18, 8));
+        assert_eq!(metrics.text_size("a\nabc", false), Vec2d::new(27, 17));
+    }
+
+    #[test]
+    fn glyphs_size_eq_text_size() {
+        let metrics = FontMetrics::new((8, 8), 0, 1, HashMap::new());
+        let text = "Hello,\nthe\nworld!";
+
+        let glyphs = metrics.glyphs(text, vec![], false);
+        assert_eq!(glyphs.size(), metrics.text_size(text, false));
+        assert_eq!(glyphs.len(), text.chars().count());
+    }
+
+    #[test]
+    fn carets() {
+        let mut glyph_widths = HashMap::new();
+        glyph_widths.insert('i', GlyphSize::new(0, 2));
+        let metrics = FontMetrics::new((8, 8), 1, 1, glyph_widths);
+
+        let carets: Vec<_> = metrics
+            .carets("ai\nb", false)
+            .into_iter()
+            .map(Vec2d::into_inner)
+            .collect();
+
+        assert_eq!(carets, [(0, 0), (9, 0), (12, 0), (0, 1), (9, 1)]);
+        assert_eq!(metrics.carets("", false), [Vec2d::new(0, 0)]);
     }
 }
@@ -1,6 +1,7 @@
 use super::{
     super::common::Rect,
-    font::Font,
+    assets::{Assets, AssetError},
+    font::{Font, FontMetrics},
     glyphs::*,
     Render,
     shader_data::UsedShader,
@@ -13,9 +14,9 @@ pub struct FontRender {
 }
 
 impl FontRender {
-    pub fn new() -> Self {
-        let font = Font::default();
-        FontRender { font, buf: Some(vec![]) }
+    pub fn new(assets: &Assets) -> Result<Self, AssetError> {
+        let font = Font::load(assets)?;
+        Ok(FontRender { font, buf: Some(vec![]) })
     }
 
     pub fn print(&self, render: &mut Render, chars: &[Char], rect: Rect<i32>) {
@@ -41,6 +42,8 @@ impl FontRender {
         }
     }
 
+    pub fn metrics(&self) -> &FontMetrics { self.font.metrics() }
+
     pub fn glyphs(&mut self, text: &str, monospaced: bool) -> Glyphs {
         self.font.glyphs(text, self.buf.take().unwrap(), monospaced)
     }
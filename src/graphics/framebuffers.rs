@@ -13,6 +13,7 @@ use super::{
 };
 
 #[derive(Debug)]
+#[allow(dead_code)]
 pub enum FramebufferError {
     RenderbufferError(RenderbufferError),
     TextureError(TextureError),
@@ -157,7 +158,7 @@ impl FramebufferSet {
 
     pub fn bind(&mut self, idx: usize) {
         match self.bound {
-            Some(i) if i == idx => return,
+            Some(i) if i == idx => (),
             _ => {
                 unsafe { FramebufferSet::bind_unsafe(self.framebuffers[idx].id) };
                 self.bound = Some(idx);
@@ -187,10 +188,33 @@ impl FramebufferSet {
         where
             S: Into<Vec2d<i32>>,
     {
+        let formats = self.texture_formats();
+        self.rebuild(size.into(), formats)
+    }
+
+    /// Recreates the `idx` texture of the active framebuffer with the new format.
+    pub fn set_texture_format(&mut self, idx: usize, format: TextureFormat)
+                              -> Result<(), FramebufferError> {
+        let mut formats = self.texture_formats();
+        formats[idx] = format;
+
+        self.rebuild(self.active().size, formats)
+    }
+
+    fn texture_formats(&self) -> Vec<TextureFormat> {
+        self.active()
+            .textures
+            .iter()
+            .map(|texture| texture.format())
+            .collect()
+    }
+
+    fn rebuild(&mut self, size: Vec2d<i32>, formats: Vec<TextureFormat>)
+               -> Result<(), FramebufferError> {
         let framebuffer = self.active_mut();
 
-        // Save old textures (It needs only texture formats)
-        let textures = std::mem::replace(&mut framebuffer.textures, vec![]);
+        // Drop old textures. Only their formats are needed
+        framebuffer.textures.clear();
 
         // Take the renderbuffer format. Leave the renderbuffer in the framebuffer
         // to delete them both.
@@ -201,7 +225,7 @@ impl FramebufferSet {
         // Recreate the framebuffer with new size
         unsafe {
             framebuffer.delete();
-            *framebuffer = Framebuffer::new(size.into());
+            *framebuffer = Framebuffer::new(size);
             FramebufferSet::bind_unsafe(framebuffer.id);
         }
 
@@ -211,8 +235,8 @@ impl FramebufferSet {
         }
 
         // Add new textures
-        for texture in textures {
-            self.add_texture(texture.format())?;
+        for format in formats {
+            self.add_texture(format)?;
         }
 
         Ok(())
@@ -0,0 +1,238 @@
+use super::{
+    super::common::{
+        Rect,
+        Vec2d,
+    },
+    transform::{identity, transform_rect},
+    Draw,
+    DrawParameters,
+    Metrics,
+};
+
+/// The rect occupied by a widget with the id of the nearest `Id` parent.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Hit {
+    pub rect: Rect<i32>,
+    pub id: Option<u32>,
+}
+
+/// The hit-test pass. It walks the `Draw` tree the same way as the `Render`,
+/// but only records rects, so it works without GL.
+#[derive(Debug)]
+pub struct HitTest<'a> {
+    metrics: &'a Metrics,
+    hits: Vec<Hit>,
+    ids: Vec<u32>,
+    clips: Vec<Rect<i32>>,
+    transforms: Vec<glm::Mat4>,
+}
+
+#[allow(dead_code)]
+impl<'a> HitTest<'a> {
+    pub fn new(metrics: &'a Metrics) -> Self {
+        HitTest {
+            metrics,
+            hits: vec![],
+            ids: vec![],
+            clips: vec![],
+            transforms: vec![],
+        }
+    }
+
+    /// Runs the pass over the widget placed in the frame.
+    pub fn run<D>(metrics: &Metrics, draw: &D, frame: Rect<i32>) -> Hits
+        where
+            D: Draw,
+    {
+        let mut test = HitTest::new(metrics);
+        draw.hit(&mut test, DrawParameters::new(frame));
+        test.finish()
+    }
+
+    pub fn metrics(&self) -> &Metrics { self.metrics }
+
+    /// Records the rect with the current id. Only the part inside the clip is recorded.
+    /// The rect is transformed, a rotated one is recorded by its bounds.
+    pub fn record(&mut self, rect: Rect<i32>) {
+        let rect = transform_rect(&self.transform(), rect);
+        let rect = match self.clip() {
+            Some(clip) => match rect.intersection(clip) {
+                Some(rect) => rect,
+                None => return,
+            },
+            None => rect,
+        };
+
+        let id = self.ids.last().copied();
+        self.hits.push(Hit { rect, id })
+    }
+
+    /// The rect that limits recorded rects, the same as `Render::clip`.
+    pub fn clip(&self) -> Option<Rect<i32>> { self.clips.last().copied() }
+
+    pub fn push_clip(&mut self, rect: Rect<i32>) {
+        let clip = nest_clip(self.clip(), transform_rect(&self.transform(), rect));
+        self.clips.push(clip)
+    }
+
+    pub fn pop_clip(&mut self) { self.clips.pop(); }
+
+    /// The transform of recorded rects, the same as `Render::transform`.
+    pub fn transform(&self) -> glm::Mat4 {
+        self.transforms.last().copied().unwrap_or_else(identity)
+    }
+
+    pub fn push_transform(&mut self, transform: &glm::Mat4) {
+        let transform = self.transform() * transform;
+        self.transforms.push(transform)
+    }
+
+    pub fn pop_transform(&mut self) { self.transforms.pop(); }
+
+    /// Sets the id of everything recorded until the `pop_id`.
+    pub fn push_id(&mut self, id: u32) { self.ids.push(id) }
+
+    pub fn pop_id(&mut self) { self.ids.pop(); }
+
+    pub fn finish(self) -> Hits { Hits { hits: self.hits } }
+}
+
+/// Intersects the clip with the parent one. If they don't intersect, the clip is empty.
+pub(super) fn nest_clip(parent: Option<Rect<i32>>, rect: Rect<i32>) -> Rect<i32> {
+    match parent {
+        Some(parent) => parent
+            .intersection(rect)
+            .unwrap_or_else(|| Rect::new(rect.pos(), (0, 0))),
+        None => rect,
+    }
+}
+
+/// Rects recorded by the `HitTest` in the drawing order, so the last ones are on top.
+#[derive(Clone, Debug, Default)]
+pub struct Hits {
+    hits: Vec<Hit>,
+}
+
+#[allow(dead_code)]
+impl Hits {
+    /// Returns the topmost widget at the point.
+    pub fn at<P>(&self, point: P) -> Option<&Hit>
+        where
+            P: Into<Vec2d<i32>>,
+    {
+        let point = point.into();
+
+        self.hits
+            .iter()
+            .rev()
+            .find(|hit| hit.rect.intersects_point(point))
+    }
+
+    /// Returns the id of the topmost widget at the point.
+    pub fn id_at<P>(&self, point: P) -> Option<u32>
+        where
+            P: Into<Vec2d<i32>>,
+    { self.at(point).and_then(|hit| hit.id) }
+
+    /// Returns the rect of the widget with the id. It's the union of everything recorded with it.
+    pub fn rect_of(&self, id: u32) -> Option<Rect<i32>> {
+        self.hits
+            .iter()
+            .filter(|hit| hit.id == Some(id))
+            .map(|hit| hit.rect)
+            .fold(None, |total, rect| Some(total.map_or(rect, |total: Rect<i32>| total.union(rect))))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item=&Hit> { self.hits.iter() }
+
+    pub fn len(&self) -> usize { self.hits.len() }
+
+    pub fn is_empty(&self) -> bool { self.hits.is_empty() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        common::Position,
+        ui::{Clip, Frame, Id, Pos, Row, Scale, Stack, Transform},
+    };
+
+    fn metrics() -> Metrics { Metrics::test() }
+
+    fn frame() -> Rect<i32> { Rect::new((0, 0), (100, 50)) }
+
+    #[test]
+    fn leaves() {
+        let metrics = metrics();
+
+        let hits = HitTest::run(&metrics, &Rect::new((10, 10), (5, 5)), frame());
+        assert_eq!(hits.len(), 1);
+        assert!(hits.at((12, 12)).is_some());
+        assert!(hits.at((15, 12)).is_none());
+
+        // 2 glyphs of 4 pixels, each followed by the indent of 1
+        let hits = HitTest::run(&metrics, &Pos::left_bot(0, 0, "ab"), frame());
+        assert_eq!(hits.at((0, 0)).unwrap().rect, Rect::new((0, 0), (10, 6)));
+    }
+
+    #[test]
+    fn topmost_and_ids() {
+        let metrics = metrics();
+
+        let ui = Stack::new()
+            .position(Position::LeftBot(0, 0))
+            .with(Id::new(1, Rect::new((0, 0), (20, 20))))
+            .with(Rect::new((10, 0), (20, 20)))
+            .with(Id::new(2, Row::new().with(Rect::new((0, 0), (5, 5))).with("ab")));
+        let ui = Pos::left_bot(0, 0, ui);
+
+        let hits = HitTest::run(&metrics, &ui, frame());
+
+        assert_eq!(hits.id_at((5, 10)), Some(1));
+        assert_eq!(hits.id_at((15, 10)), None);
+        assert_eq!(hits.id_at((2, 2)), Some(2));
+        assert_eq!(hits.id_at((50, 40)), None);
+
+        // The row is 5 + 10 wide and aligned to the top of 6 pixels
+        assert_eq!(hits.rect_of(2), Some(Rect::new((0, 0), (15, 6))));
+        assert_eq!(hits.id_at((7, 5)), Some(2));
+        assert_eq!(hits.rect_of(3), None);
+    }
+
+    #[test]
+    fn clips() {
+        let metrics = metrics();
+
+        let content = Clip::new(Rect::new((0, 0), (40, 40)));
+        let inner = Frame::rect(Rect::new((5, 5), (20, 20)), content);
+        let ui = Frame::rect(Rect::new((0, 0), (10, 10)), Clip::new(inner));
+
+        let hits = HitTest::run(&metrics, &ui, frame());
+        assert_eq!(hits.at((7, 7)).unwrap().rect, Rect::new((5, 5), (5, 5)));
+        assert!(hits.at((12, 7)).is_none());
+        assert!(hits.at((2, 2)).is_none());
+
+        let outside = Frame::rect(Rect::new((20, 20), (5, 5)), Rect::new((0, 0), (5, 5)));
+        let ui = Frame::rect(Rect::new((0, 0), (10, 10)), Clip::new(outside));
+        assert!(HitTest::run(&metrics, &ui, frame()).is_empty());
+    }
+
+    #[test]
+    fn transforms() {
+        let metrics = metrics();
+
+        let scaled = Scale::new(2.0, Id::new(1, "ab")).pivot((0.0, 0.0));
+        let ui = Transform::translate((5.0, 0.0), Pos::left_bot(10, 10, scaled));
+
+        let hits = HitTest::run(&metrics, &ui, frame());
+        assert_eq!(hits.rect_of(1), Some(Rect::new((15, 10), (20, 12))));
+
+        // The clip is transformed too
+        let clipped = Frame::rect(Rect::new((0, 0), (2, 2)), Clip::new(Rect::new((0, 0), (9, 9))));
+        let ui = Pos::left_bot(0, 0, Scale::new(2.0, clipped).pivot((0.0, 0.0)));
+
+        let hits = HitTest::run(&metrics, &ui, frame());
+        assert_eq!(hits.at((1, 1)).unwrap().rect, Rect::new((0, 0), (4, 4)));
+    }
+}
@@ -0,0 +1,35 @@
+use super::{
+    super::common::Vec2d,
+    font::FontMetrics,
+    FontStyle,
+};
+
+/// Everything needed to measure widgets without a GL context.
+#[derive(Clone, Debug)]
+pub struct Metrics {
+    font: FontMetrics,
+}
+
+#[allow(dead_code)]
+impl Metrics {
+    pub fn new(font: FontMetrics) -> Self { Metrics { font } }
+
+    pub fn font(&self) -> &FontMetrics { &self.font }
+
+    pub fn text_size(&self, text: &str, style: &FontStyle) -> Vec2d<i32> {
+        self.font.text_size(text, style.monospaced)
+    }
+
+    /// Returns caret positions of the text, see `FontMetrics::carets`.
+    pub fn carets(&self, text: &str, style: &FontStyle) -> Vec<Vec2d<i32>> {
+        self.font.carets(text, style.monospaced)
+    }
+}
+
+#[cfg(test)]
+impl Metrics {
+    /// Metrics without glyph widths, where each glyph is 4 x 6 pixels with the indent of 1.
+    pub fn test() -> Self {
+        Metrics::new(FontMetrics::new((4, 6), 1, 1, std::collections::HashMap::new()))
+    }
+}
@@ -2,24 +2,35 @@
 mod macros;
 
 mod accept;
+mod assets;
+mod atlas;
+mod batch;
 mod debug;
 mod draw;
 mod font;
 mod font_render;
 mod framebuffers;
 mod glyphs;
+mod hit;
+mod metrics;
 mod pages;
-mod rect_render;
 mod render;
 mod renderbuffer;
 mod shader_data;
 mod shaders;
 mod texture;
+mod tint;
+mod transform;
 mod uniforms;
 mod viewport;
 mod window;
 
+pub use assets::{Assets, AssetError};
+pub use atlas::{AtlasId, TextureAtlas};
 pub use draw::*;
+pub use hit::{HitTest, Hits};
+pub use metrics::Metrics;
 pub use render::Render;
 pub use texture::{Texture, TextureError};
+pub use transform::{around, translation};
 pub use window::Window;
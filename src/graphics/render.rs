@@ -1,24 +1,37 @@
+use std::rc::Rc;
+
 use super::{
-    super::common::*,
+    super::{
+        common::*,
+        input::{Focus, Input},
+    },
+    batch::{BatchState, QuadBatch},
+    assets::{Assets, AssetError},
     Draw,
     DrawParameters,
-    FontStyle,
+    Metrics,
+    Hits,
+    HitTest,
+    hit::nest_clip,
+    transform::{identity, transform_rect},
     font_render::FontRender,
     framebuffers::{FramebufferSet, FramebufferError},
-    rect_render::RectRender,
     shader_data::*,
     shaders::*,
     texture::{Texture, Format as TextureFormat},
+    tint::Tints,
     renderbuffer::Format as RenderbufferFormat,
     uniforms::UniformError,
     viewport::Viewport,
 };
 
 #[derive(Debug)]
+#[allow(dead_code)]
 pub enum RenderError {
     UniformError(UniformError),
     ShaderError(ShaderError),
     FramebufferError(FramebufferError),
+    AssetError(AssetError),
     WrongPixelSize,
 }
 
@@ -34,6 +47,10 @@ impl From<FramebufferError> for RenderError {
     fn from(e: FramebufferError) -> Self { RenderError::FramebufferError(e) }
 }
 
+impl From<AssetError> for RenderError {
+    fn from(e: AssetError) -> Self { RenderError::AssetError(e) }
+}
+
 #[derive(Debug)]
 pub struct Render {
     viewport: Viewport,
@@ -41,16 +58,31 @@ pub struct Render {
     framebuffers: FramebufferSet,
     size: Vec2d<i32>,
     pixel_size: i32,
-    rect_render: RectRender,
+    srgb: bool,
+    tints: Tints,
+    clips: Vec<Rect<i32>>,
+    transforms: Vec<glm::Mat4>,
+    batch: QuadBatch,
+    texture: Option<Rc<Texture>>,
+    color: Color,
+    draw_calls: usize,
     font_render: Option<FontRender>,
+    metrics: Metrics,
+    assets: Assets,
+    input: Input,
+    focus: Focus,
     base_data: BaseData,
     post_data: PostData,
     shader_data: ShaderData,
 }
 
 impl Render {
-    pub fn new(context: &glutin::WindowedContext<glutin::PossiblyCurrent>, pixel_size: i32)
-               -> Result<Self, RenderError> {
+    /// Creates the render, which loads the font and other files by the `assets`.
+    pub fn new(
+        context: &glutin::WindowedContext<glutin::PossiblyCurrent>,
+        pixel_size: i32,
+        assets: Assets,
+    ) -> Result<Self, RenderError> {
         if pixel_size <= 0 {
             return Err(RenderError::WrongPixelSize);
         }
@@ -61,12 +93,8 @@ impl Render {
 
         let mut shaders = Render::make_shader_set()?;
 
-        let (w, h): (i32, i32) = context
-            .window()
-            .inner_size()
-            .to_logical::<i32>(context.window().scale_factor())
-            .into();
-
+        // Physical pixels, the same as of `Resized` and cursor events
+        let (w, h): (i32, i32) = context.window().inner_size().into();
         let size: Vec2d<i32> = (w / pixel_size, h / pixel_size).into();
 
         let projection = Render::make_ortho(size.cast::<f32>());
@@ -79,14 +107,28 @@ impl Render {
         framebuffers.add_texture(TextureFormat::RGB)?;
         framebuffers.add_renderbuffer(RenderbufferFormat::Depth24)?;
 
+        let font_render = FontRender::new(&assets)?;
+        let metrics = Metrics::new(font_render.metrics().clone());
+
         Ok(Render {
             viewport: Viewport::new(size),
             shaders,
             framebuffers,
             size,
             pixel_size,
-            rect_render: RectRender::new(0, 1),
-            font_render: Some(FontRender::new()),
+            srgb: false,
+            tints: Tints::new(),
+            clips: vec![],
+            transforms: vec![],
+            batch: QuadBatch::new(0, 1, 2),
+            texture: None,
+            color: Color::white(),
+            draw_calls: 0,
+            font_render: Some(font_render),
+            metrics,
+            assets,
+            input: Input::new(h, pixel_size),
+            focus: Focus::new(),
             base_data,
             post_data,
             shader_data,
@@ -139,7 +181,55 @@ impl Render {
     #[allow(dead_code)]
     pub fn size(&self) -> Vec2d<i32> { self.size }
 
+    #[allow(dead_code)]
+    pub fn metrics(&self) -> &Metrics { &self.metrics }
+
+    /// The cache of loaded files.
+    pub fn assets(&self) -> &Assets { &self.assets }
+
+    #[allow(dead_code)]
+    pub fn assets_mut(&mut self) -> &mut Assets { &mut self.assets }
+
+    /// The input of the current frame in coordinates of the current transform,
+    /// so widgets see the pointer where they are drawn.
+    #[allow(dead_code)]
+    pub fn input(&self) -> &Input { &self.input }
+
+    pub(super) fn input_mut(&mut self) -> &mut Input { &mut self.input }
+
+    /// The keyboard focus. Focusable widgets are added to it while drawing.
+    pub fn focus(&self) -> &Focus { &self.focus }
+
+    pub fn focus_mut(&mut self) -> &mut Focus { &mut self.focus }
+
+    /// Moves the focus by keys and drops events of the frame.
+    pub(super) fn end_input_frame(&mut self) {
+        self.focus.end_frame(&self.input);
+        self.input.end_frame();
+    }
+
+    /// Whether colours are blended in linear space. Then textures should be loaded as sRGB
+    /// and colours passed to the `Render` are decoded from sRGB.
+    pub fn is_srgb(&self) -> bool { self.srgb }
+
+    /// Switches the gamma-correct rendering. It recreates the offscreen frame.
+    pub fn set_srgb(&mut self, srgb: bool) -> Result<(), RenderError> {
+        if self.srgb == srgb {
+            return Ok(());
+        }
+
+        let format = if srgb { TextureFormat::SRGBA } else { TextureFormat::RGB };
+
+        self.framebuffers.bind(0);
+        self.framebuffers.set_texture_format(0, format)?;
+        self.post_data.srgb.set_value(srgb);
+        self.srgb = srgb;
+
+        Ok(())
+    }
+
     pub(super) fn resize(&mut self, size: Vec2d<i32>) {
+        self.input.resize(size.y);
         let size = size / self.pixel_size;
 
         let projection = Render::make_ortho(size.cast::<f32>());
@@ -154,9 +244,23 @@ impl Render {
     pub(super) fn begin_draw_frame(&mut self) {
         self.framebuffers.bind(0);
         self.viewport.resize(self.framebuffers.active().size());
+        self.clips.clear();
+        self.apply_clip();
+        self.transforms.clear();
+        self.apply_transform();
+
+        // Shaders output linear colours, GL encodes them and blends in linear space
+        if self.srgb {
+            unsafe { gl::Enable(gl::FRAMEBUFFER_SRGB) }
+        }
     }
 
     pub(super) fn end_draw_frame(&mut self) {
+        self.clips.clear();
+        self.apply_clip();
+        self.transforms.clear();
+        self.apply_transform();
+
         self.framebuffers
             .active()
             .textures()
@@ -164,6 +268,11 @@ impl Render {
             .enumerate()
             .for_each(|(i, texture)| texture.bind(i as u32));
 
+        // The post shader encodes colours by itself
+        if self.srgb {
+            unsafe { gl::Disable(gl::FRAMEBUFFER_SRGB) }
+        }
+
         self.framebuffers.bind_default();
         self.viewport.resize(self.size * self.pixel_size);
         self.clear(Color::black());
@@ -175,10 +284,16 @@ impl Render {
             false,
         );
 
+        self.flush();
+        self.draw_calls = self.batch.take_draw_calls();
+
         super::debug::unwrap_error();
     }
 
-    pub fn clear(&self, color: Color) {
+    pub fn clear(&mut self, color: Color) {
+        self.flush();
+        let color = self.output_color(color);
+
         unsafe {
             gl::ClearColor(color.r(), color.g(), color.b(), color.a());
             gl::Clear(gl::COLOR_BUFFER_BIT);
@@ -189,6 +304,86 @@ impl Render {
         self.draw_rect_accept(UsedShader::Base, rect, None, true);
     }
 
+    /// The rect that limits drawing, in UI pixels.
+    #[allow(dead_code)]
+    pub fn clip(&self) -> Option<Rect<i32>> { self.clips.last().copied() }
+
+    /// Limits drawing to the rect until the `pop_clip`. Nested clips are intersected.
+    /// The rect is transformed, a rotated one limits drawing by its bounds.
+    pub fn push_clip(&mut self, rect: Rect<i32>) {
+        let clip = nest_clip(self.clip(), transform_rect(&self.transform(), rect));
+
+        self.clips.push(clip);
+        self.apply_clip();
+    }
+
+    /// Restores the previous clip.
+    pub fn pop_clip(&mut self) {
+        self.clips.pop();
+        self.apply_clip();
+    }
+
+    fn apply_clip(&mut self) {
+        self.flush();
+
+        let clip = match self.clip() {
+            Some(clip) => clip,
+            None => {
+                unsafe { gl::Disable(gl::SCISSOR_TEST) }
+                return;
+            }
+        };
+
+        // UI pixels to pixels of the offscreen frame
+        let frame = self.framebuffers.active().size();
+        let scale = |v: i32, frame: i32, ui: i32| v * frame / ui.max(1);
+
+        let left = scale(clip.left(), frame.x, self.size.x);
+        let right = scale(clip.right(), frame.x, self.size.x);
+        let bot = scale(clip.bot(), frame.y, self.size.y);
+        let top = scale(clip.top(), frame.y, self.size.y);
+
+        unsafe {
+            gl::Enable(gl::SCISSOR_TEST);
+            gl::Scissor(left, bot, right - left, top - bot);
+        }
+    }
+
+    /// The transform of the drawing in UI pixels.
+    pub fn transform(&self) -> glm::Mat4 {
+        self.transforms.last().copied().unwrap_or_else(identity)
+    }
+
+    /// Transforms drawing until the `pop_transform`. Nested transforms are composed,
+    /// so the new one is applied before the current.
+    pub fn push_transform(&mut self, transform: &glm::Mat4) {
+        let transform = self.transform() * transform;
+
+        self.transforms.push(transform);
+        self.apply_transform();
+    }
+
+    /// Restores the previous transform.
+    pub fn pop_transform(&mut self) {
+        self.transforms.pop();
+        self.apply_transform();
+    }
+
+    fn apply_transform(&mut self) {
+        self.flush();
+
+        let transform = self.transform();
+        self.input.set_transform(&transform);
+        self.shader_data.model.set_value(transform)
+    }
+
+    /// Fills the rect with the colour.
+    pub fn fill_rect(&mut self, rect: Rect<i32>, color: Color) {
+        self.set_color(color);
+        self.unset_texture();
+        self.draw_rect(rect.cast());
+    }
+
     #[allow(dead_code)]
     pub fn draw_rect_st(&mut self, rect: Rect<f32>, st: Rect<f32>) {
         self.draw_rect_accept(UsedShader::Base, rect, Some(st), true);
@@ -201,42 +396,78 @@ impl Render {
         st: Option<Rect<f32>>,
         flip_v: bool,
     ) {
-        self.shaders.use_shader(shader as usize);
+        // The post shader reads frame textures that are bound by the frame
+        let texture = match shader {
+            UsedShader::Post => None,
+            _ => self.texture.clone(),
+        };
+
+        let state = BatchState::new(shader, texture.as_deref());
+        if !self.batch.accepts(state) {
+            self.flush();
+        }
+
+        self.batch.push(shader, texture.as_ref(), rect, st, flip_v, self.color);
+    }
+
+    /// Draws accumulated quads. It must be called before changing anything that affects drawing.
+    fn flush(&mut self) {
+        let state = match self.batch.state() {
+            Some(state) => state,
+            None => return,
+        };
+
+        self.shaders.use_shader(state.shader as usize);
         self.shader_data.accept(&self.shaders);
 
-        if shader == UsedShader::Base {
+        if state.shader == UsedShader::Base {
+            self.base_data.draw_texture.set_value(state.texture.is_some());
             self.base_data.draw_texture.accept(&self.shaders);
         }
 
-        if shader == UsedShader::Post {
+        if state.shader == UsedShader::Post {
             self.post_data.frame.accept(&self.shaders);
+            self.post_data.srgb.accept(&self.shaders);
+        }
+
+        if let Some(texture) = state.texture {
+            unsafe {
+                gl::ActiveTexture(gl::TEXTURE0 + self.shader_data.texture0.get() as u32);
+                gl::BindTexture(gl::TEXTURE_2D, texture);
+            }
         }
 
-        self.rect_render.draw(rect, st, flip_v);
+        self.batch.flush();
     }
 
+    /// The number of draw calls of the previous frame.
+    pub fn draw_calls(&self) -> usize { self.draw_calls }
+
     pub fn draw<D>(&mut self, draw: &D)
         where
             D: Draw,
     {
-        draw.draw(self, DrawParameters {
-            color: Color::white(),
-            position: Position::default(),
-            frame: self.size.into_rect(),
-            font_style: FontStyle::default(),
-        })
+        let params = DrawParameters::new(self.size.into_rect());
+        draw.draw(self, params)
     }
 
-    pub fn set_texture(&mut self, texture: &Texture) {
-        const TEXTURE0_UNIT: i32 = 0;
+    #[allow(dead_code)]
+    pub fn measure<D>(&self, draw: &D) -> Vec2d<i32>
+        where
+            D: Draw,
+    { draw.measure(&self.metrics, &DrawParameters::new(self.size.into_rect())) }
 
-        self.shader_data.texture0.set_value(TEXTURE0_UNIT);
-        texture.bind(self.shader_data.texture0.get() as u32);
+    /// Finds where the widget's parts are drawn.
+    #[allow(dead_code)]
+    pub fn hit_test<D>(&self, draw: &D) -> Hits
+        where
+            D: Draw,
+    { HitTest::run(&self.metrics, draw, self.size.into_rect()) }
 
-        self.base_data.draw_texture.set_value(true);
-    }
+    /// Sets the texture of the next drawing. The render keeps it until it's drawn.
+    pub fn set_texture(&mut self, texture: &Rc<Texture>) { self.texture = Some(Rc::clone(texture)) }
 
-    pub fn unset_texture(&mut self) { self.base_data.draw_texture.set_value(false) }
+    pub fn unset_texture(&mut self) { self.texture = None }
 
     pub fn print(&mut self, text: &str, params: &DrawParameters) {
         let mut font = self.font_render.take().unwrap();
@@ -246,15 +477,36 @@ impl Render {
 
         if let Some(shadow) = &params.font_style.shadow {
             self.set_color(shadow.color);
-            font.print(self, &*glyphs, rect.translated(shadow.delta));
+            font.print(self, &glyphs, rect.translated(shadow.delta));
         }
 
         self.set_color(params.color);
-        font.print(self, &*glyphs, rect);
+        font.print(self, &glyphs, rect);
         font.print_end(glyphs.into_inner());
 
         self.font_render = Some(font);
     }
 
-    pub fn set_color(&mut self, color: Color) { self.shader_data.col.set_value(color) }
+    /// Sets the colour of the next drawing. It's multiplied by the current tint.
+    pub fn set_color(&mut self, color: Color) {
+        self.color = self.output_color(color * self.tint())
+    }
+
+    /// The tint that multiplies every colour, so the whole subtree is faded or tinted together.
+    pub fn tint(&self) -> Color { self.tints.current() }
+
+    /// Multiplies the tint until the `pop_tint`.
+    pub fn push_tint(&mut self, tint: Color) { self.tints.push(tint) }
+
+    /// Restores the previous tint.
+    pub fn pop_tint(&mut self) { self.tints.pop() }
+
+    /// Converts the colour to the space of the frame.
+    fn output_color(&self, color: Color) -> Color {
+        if self.srgb {
+            color.to_linear()
+        } else {
+            color
+        }
+    }
 }
@@ -1,5 +1,4 @@
 use super::{
-    super::common::Color,
     shaders::ShaderSet,
     uniforms::{
         Uniform,
@@ -33,6 +32,7 @@ impl BaseData {
 #[derive(Debug)]
 pub struct PostData {
     pub frame: Uniform<i32>,
+    pub srgb: Uniform<bool>,
 }
 
 impl PostData {
@@ -41,6 +41,7 @@ impl PostData {
 
         Ok(PostData {
             frame: shaders.make_uniform(0, c_str!("frame"))?,
+            srgb: shaders.make_uniform(false, c_str!("srgb"))?,
         })
     }
 }
@@ -48,8 +49,8 @@ impl PostData {
 #[derive(Debug)]
 pub struct ShaderData {
     pub projection: SharedUniform<glm::Mat4>,
+    pub model: SharedUniform<glm::Mat4>,
     pub texture0: SharedUniform<i32>,
-    pub col: SharedUniform<Color>,
 }
 
 impl ShaderData {
@@ -58,14 +59,14 @@ impl ShaderData {
 
         Ok(ShaderData {
             projection: shaders.make_shared(projection, c_str!("projection"), &used_shaders)?,
+            model: shaders.make_shared(glm::identity(), c_str!("model"), &used_shaders)?,
             texture0: shaders.make_shared(0, c_str!("texture0"), &used_shaders)?,
-            col: shaders.make_shared(Color::white(), c_str!("col"), &used_shaders)?,
         })
     }
 
     pub fn accept(&self, shader: &ShaderSet) {
-        self.projection.accept(&shader);
-        self.texture0.accept(&shader);
-        self.col.accept(&shader);
+        self.projection.accept(shader);
+        self.model.accept(shader);
+        self.texture0.accept(shader);
     }
 }
@@ -13,6 +13,7 @@ use super::{
 const INFO_LOG_SIZE: usize = 512;
 
 #[derive(Debug)]
+#[allow(dead_code, clippy::enum_variant_names)]
 pub enum ShaderError {
     IOError(std::io::Error),
     CompileError(std::ffi::CString),
@@ -44,7 +45,7 @@ impl Shader {
             gl::ShaderSource(
                 shader_id,
                 1,
-                &(code.as_ptr() as *const i8),
+                &code.as_ptr(),
                 std::ptr::null(),
             );
 
@@ -172,7 +173,7 @@ impl ShaderSet {
 
     pub fn use_shader(&mut self, idx: usize) {
         match self.used {
-            Some(i) if i == idx => return,
+            Some(i) if i == idx => (),
             _ => {
                 unsafe { ShaderSet::use_shader_unsafe(self.shaders[idx].id) }
                 self.used = Some(idx);
@@ -236,7 +237,7 @@ impl ShaderSet {
                     (location, shader_idx)
                 });
 
-        Ok(SharedUniform::new(value, data)?)
+        SharedUniform::new(value, data)
     }
 }
 
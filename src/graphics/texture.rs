@@ -7,11 +7,16 @@ use im::{
 use crate::common::Vec2d;
 
 #[derive(Copy, Clone, Debug)]
+#[allow(clippy::upper_case_acronyms)]
 pub enum Format {
     R,
     RG,
     RGB,
     RGBA,
+    /// sRGB encoded `RGB`. It's converted to linear space on sampling.
+    SRGB,
+    /// sRGB encoded `RGBA`. It's converted to linear space on sampling.
+    SRGBA,
 }
 
 impl Format {
@@ -19,8 +24,25 @@ impl Format {
         match self {
             Format::R => gl::RED,
             Format::RG => gl::RG,
-            Format::RGB => gl::RGB,
-            Format::RGBA => gl::RGBA,
+            Format::RGB | Format::SRGB => gl::RGB,
+            Format::RGBA | Format::SRGBA => gl::RGBA,
+        }
+    }
+
+    pub fn internal_format(&self) -> u32 {
+        match self {
+            Format::SRGB => gl::SRGB8,
+            Format::SRGBA => gl::SRGB8_ALPHA8,
+            _ => self.format(),
+        }
+    }
+
+    /// Returns the sRGB variant of the format. Formats without colour channels stay the same.
+    pub fn srgb(self) -> Format {
+        match self {
+            Format::RGB => Format::SRGB,
+            Format::RGBA => Format::SRGBA,
+            other => other,
         }
     }
 
@@ -28,13 +50,14 @@ impl Format {
         match self {
             Format::R => 1,
             Format::RG => 2,
-            Format::RGB => 3,
-            Format::RGBA => 4,
+            Format::RGB | Format::SRGB => 3,
+            Format::RGBA | Format::SRGBA => 4,
         }
     }
 }
 
 #[derive(Debug)]
+#[allow(dead_code)]
 pub enum TextureError {
     ImageError(ImageError),
     NegativeSize,
@@ -69,7 +92,24 @@ impl Texture {
         Texture::from_image(&img)
     }
 
+    /// Loads the texture, which colours are sRGB encoded.
+    pub fn from_file_srgb<S>(file: S) -> Result<Self, TextureError>
+        where
+            S: AsRef<str>,
+    {
+        let img = im::open(file.as_ref())?;
+        Texture::from_image_srgb(&img)
+    }
+
     pub fn from_image(img: &DynamicImage) -> Result<Self, TextureError> {
+        Texture::from_image_with(img, false)
+    }
+
+    pub fn from_image_srgb(img: &DynamicImage) -> Result<Self, TextureError> {
+        Texture::from_image_with(img, true)
+    }
+
+    fn from_image_with(img: &DynamicImage, srgb: bool) -> Result<Self, TextureError> {
         let (format, raw) = match img {
             DynamicImage::ImageLuma8(data) => (Format::R, data.as_ref()),
             DynamicImage::ImageLumaA8(data) => (Format::RG, data.as_ref()),
@@ -78,6 +118,8 @@ impl Texture {
             _ => return Err(TextureError::UnsupportedFormat),
         };
 
+        let format = if srgb { format.srgb() } else { format };
+
         let (width, height) = img.dimensions();
         Texture::from_raw(Some(raw), format, (width as i32, height as i32).into())
     }
@@ -109,7 +151,7 @@ impl Texture {
             gl::TexImage2D(
                 gl::TEXTURE_2D,
                 0,
-                format.format() as i32,
+                format.internal_format() as i32,
                 width,
                 height,
                 0,
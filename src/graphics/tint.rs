@@ -0,0 +1,45 @@
+use super::super::common::Color;
+
+/// Tints of nested widgets. Each one multiplies the tints pushed before it.
+#[derive(Debug, Default)]
+pub struct Tints(Vec<Color>);
+
+impl Tints {
+    pub fn new() -> Self { Tints(vec![]) }
+
+    /// The tint that multiplies every colour, or white if nothing is pushed.
+    pub fn current(&self) -> Color { self.0.last().copied().unwrap_or_else(Color::white) }
+
+    pub fn push(&mut self, tint: Color) {
+        let tint = self.current() * tint;
+        self.0.push(tint)
+    }
+
+    pub fn pop(&mut self) { self.0.pop(); }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::Opacity;
+
+    #[test]
+    fn nested() {
+        let orange = Color::rgb(1.0, 0.5, 0.0);
+        let ui = Opacity::new(0.5, Opacity::tint(orange.with_alpha(0.5), "a"));
+
+        // Pushed as `Opacity::draw` does
+        let mut tints = Tints::new();
+        tints.push(ui.color());
+        tints.push((*ui).color());
+        assert_eq!(tints.current(), orange.with_alpha(0.25));
+
+        let red = Color::rgb(1.0, 0.0, 0.0);
+        assert_eq!(red * tints.current(), red.with_alpha(0.25));
+
+        tints.pop();
+        assert_eq!(tints.current(), Color::white().with_alpha(0.5));
+        tints.pop();
+        assert_eq!(tints.current(), Color::white());
+    }
+}
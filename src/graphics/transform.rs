@@ -0,0 +1,80 @@
+use super::super::common::{
+    Rect,
+    Vec2d,
+};
+
+/// The transform that changes nothing.
+pub fn identity() -> glm::Mat4 { glm::identity() }
+
+/// Moves by the delta in UI pixels.
+pub fn translation<D>(delta: D) -> glm::Mat4
+    where
+        D: Into<Vec2d<f32>>,
+{
+    let delta = delta.into();
+    glm::translation(&glm::vec3(delta.x, delta.y, 0.0))
+}
+
+/// Applies the transform around the point instead of the origin.
+pub fn around<P>(point: P, transform: &glm::Mat4) -> glm::Mat4
+    where
+        P: Into<Vec2d<f32>>,
+{
+    let point = point.into();
+    translation(point) * transform * translation((-point.x, -point.y))
+}
+
+/// Returns the rect that bounds the transformed one.
+/// It's exact if the transform keeps the axes, e.g. it doesn't rotate by an odd angle.
+pub fn transform_rect(transform: &glm::Mat4, rect: Rect<i32>) -> Rect<i32> {
+    // Ignores the float error of the rotation, so 90 degrees keep pixels
+    const EPS: f32 = 0.001;
+
+    let rect: Rect<f32> = rect.cast();
+    let corners = [
+        (rect.left(), rect.bot()),
+        (rect.right(), rect.bot()),
+        (rect.left(), rect.top()),
+        (rect.right(), rect.top()),
+    ];
+
+    let (min, max) = corners
+        .iter()
+        .map(|&(x, y)| transform * glm::vec4(x, y, 0.0, 1.0))
+        .map(|p| Vec2d::new(p.x, p.y))
+        .fold(
+            (Vec2d::new(f32::MAX, f32::MAX), Vec2d::new(f32::MIN, f32::MIN)),
+            |(min, max), p| (min.min(p), max.max(p)),
+        );
+
+    let floor = |v: f32| (v + EPS).floor() as i32;
+    let ceil = |v: f32| (v - EPS).ceil() as i32;
+
+    Rect::from_corners((floor(min.x), floor(min.y)), (ceil(max.x), ceil(max.y)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rects() {
+        let rect = Rect::new((2, 2), (4, 2));
+
+        assert_eq!(transform_rect(&identity(), rect), rect);
+        assert_eq!(transform_rect(&translation((1.0, -2.0)), rect), Rect::new((3, 0), (4, 2)));
+
+        let scale = glm::scaling(&glm::vec3(2.0, 0.5, 1.0));
+        let scale = around((4.0, 3.0), &scale);
+        assert_eq!(transform_rect(&scale, rect), Rect::new((0, 2), (8, 2)));
+
+        let rotation = glm::rotation(std::f32::consts::FRAC_PI_2, &glm::vec3(0.0, 0.0, 1.0));
+        let rotation = around((4.0, 3.0), &rotation);
+        assert_eq!(transform_rect(&rotation, rect), Rect::new((3, 1), (2, 4)));
+
+        // The bounds of the odd angle
+        let rotation = glm::rotation(std::f32::consts::FRAC_PI_4, &glm::vec3(0.0, 0.0, 1.0));
+        let rotated = transform_rect(&rotation, Rect::new((0, 0), (2, 2)));
+        assert_eq!(rotated, Rect::new((-2, 0), (4, 3)));
+    }
+}
@@ -1,4 +1,7 @@
-use super::render::Render;
+use super::{
+    assets::Assets,
+    render::Render,
+};
 use crate::{
     common::{
         Color,
@@ -14,7 +17,8 @@ pub struct Window {
 }
 
 impl Window {
-    pub fn new<S>(title: S, (w, h): (u32, u32), pixel_size: i32) -> Self
+    /// Creates the window, its render loads files by the `assets`.
+    pub fn new<S>(title: S, (w, h): (u32, u32), pixel_size: i32, assets: Assets) -> Self
         where
             S: Into<String>,
     {
@@ -36,7 +40,7 @@ impl Window {
             context.make_current().unwrap()
         };
 
-        let render = Render::new(&context, pixel_size).unwrap();
+        let render = Render::new(&context, pixel_size, assets).unwrap();
 
         Window {
             context,
@@ -53,12 +57,18 @@ impl Window {
         self
     }
 
+    /// Enables the gamma-correct rendering. Images must be created after this call.
+    #[allow(dead_code)]
+    pub fn with_srgb(mut self) -> Self {
+        self.render.set_srgb(true).unwrap();
+        self
+    }
+
     pub fn run<F>(self, mut draw_frame_fn: F)
         where
             F: FnMut(&mut Render) + 'static,
     {
         let mut render = self.render;
-        let mut focused = true;
         let context = self.context;
         let bg = self.bg;
 
@@ -72,17 +82,20 @@ impl Window {
 
             match event {
                 Event::WindowEvent { event, .. } => match event {
+                    _ if render.input_mut().handle(&event) => (),
                     WindowEvent::Resized(size) => {
                         let (w, h) = size.into();
                         render.resize(Vec2d::new(w, h));
                         context.resize(size);
                     }
                     WindowEvent::CloseRequested => { *control_flow = ControlFlow::Exit }
-                    WindowEvent::Focused(flag) => { focused = flag }
+                    WindowEvent::Focused(flag) => render.input_mut().set_focused(flag),
                     _ => (),
                 }
                 Event::NewEvents(StartCause::Poll) => {
-                    if !focused { return; }
+                    // The unfocused window draws only the frame with releases of held input
+                    let input = render.input();
+                    if !input.is_focused() && input.events().next().is_none() { return; }
 
                     render.begin_draw_frame();
                     render.clear(bg);
@@ -90,6 +103,7 @@ impl Window {
                     draw_frame_fn(&mut render);
 
                     render.end_draw_frame();
+                    render.end_input_frame();
 
                     context.swap_buffers().unwrap();
                 }
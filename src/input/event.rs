@@ -0,0 +1,75 @@
+use crate::common::Vec2d;
+
+pub use glutin::event::VirtualKeyCode as Key;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum MouseButton {
+    Left,
+    Right,
+    Middle,
+    Other(u8),
+}
+
+impl From<glutin::event::MouseButton> for MouseButton {
+    fn from(button: glutin::event::MouseButton) -> Self {
+        use glutin::event::MouseButton as B;
+
+        match button {
+            B::Left => MouseButton::Left,
+            B::Right => MouseButton::Right,
+            B::Middle => MouseButton::Middle,
+            B::Other(n) => MouseButton::Other(n),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Modifiers {
+    pub shift: bool,
+    pub ctrl: bool,
+    pub alt: bool,
+    pub logo: bool,
+}
+
+impl From<glutin::event::ModifiersState> for Modifiers {
+    fn from(state: glutin::event::ModifiersState) -> Self {
+        Modifiers {
+            shift: state.shift(),
+            ctrl: state.ctrl(),
+            alt: state.alt(),
+            logo: state.logo(),
+        }
+    }
+}
+
+/// An input event. Positions are in UI pixels with the origin at the bottom left corner,
+/// the same as the `Render` uses.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Event {
+    MouseMove(Vec2d<i32>),
+    /// The cursor has left the window.
+    MouseLeave,
+    MouseDown(MouseButton, Vec2d<i32>),
+    MouseUp(MouseButton, Vec2d<i32>),
+    /// The scroll in lines. Positive values scroll up and right.
+    Scroll(Vec2d<f32>),
+    KeyDown(Key),
+    KeyUp(Key),
+    /// A typed character. Control characters are sent as `KeyDown` only.
+    Text(char),
+}
+
+impl Event {
+    /// Returns the event with the position mapped by the function.
+    pub fn map_pos<F>(self, f: F) -> Self
+        where
+            F: FnOnce(Vec2d<i32>) -> Vec2d<i32>,
+    {
+        match self {
+            Event::MouseMove(pos) => Event::MouseMove(f(pos)),
+            Event::MouseDown(button, pos) => Event::MouseDown(button, f(pos)),
+            Event::MouseUp(button, pos) => Event::MouseUp(button, f(pos)),
+            event => event,
+        }
+    }
+}
@@ -0,0 +1,227 @@
+use super::{
+    Event,
+    Input,
+    Key,
+};
+use crate::common::Color;
+
+/// How the focusable widget is reached from the keyboard.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct TabStop {
+    /// Stops with lower indices are reached first, equal ones are in the tree order.
+    /// Negative stops are skipped by Tab and arrows, they are focused only by the mouse.
+    pub index: i32,
+    /// Arrows move the focus between stops of the same group, Tab leaves the group.
+    pub group: Option<u32>,
+}
+
+#[allow(dead_code)]
+impl TabStop {
+    pub fn new(index: i32) -> Self {
+        TabStop {
+            index,
+            group: None,
+        }
+    }
+
+    pub fn group(mut self, group: u32) -> Self {
+        self.group = Some(group);
+        self
+    }
+
+    fn is_reachable(&self) -> bool { self.index >= 0 }
+}
+
+/// The place of the focusable widget in the current frame.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct FocusSlot(usize);
+
+/// The owner of the keyboard focus.
+///
+/// Focusable widgets are added while drawing, so slots are in the tree order.
+/// The focus is kept by the slot, so the tree must be the same from frame to frame.
+/// Tab, Shift+Tab and arrows move it at the end of the frame.
+#[derive(Debug)]
+pub struct Focus {
+    focused: Option<usize>,
+    stops: Vec<TabStop>,
+    ring: Option<Color>,
+}
+
+#[allow(dead_code)]
+impl Focus {
+    pub fn new() -> Self {
+        Focus {
+            focused: None,
+            stops: vec![],
+            ring: Some(Color::rgb(0.3, 0.6, 1.0)),
+        }
+    }
+
+    /// Adds the focusable widget to the current frame.
+    pub fn add(&mut self, stop: TabStop) -> FocusSlot {
+        self.stops.push(stop);
+        FocusSlot(self.stops.len() - 1)
+    }
+
+    pub fn is_focused(&self, slot: FocusSlot) -> bool { self.focused == Some(slot.0) }
+
+    /// Focuses the slot, or drops the focus if the slot has it.
+    pub fn set(&mut self, slot: FocusSlot, focused: bool) {
+        if focused {
+            self.focused = Some(slot.0);
+        } else if self.is_focused(slot) {
+            self.focused = None;
+        }
+    }
+
+    pub fn clear(&mut self) { self.focused = None }
+
+    /// The colour of the ring around the focused widget.
+    pub fn ring(&self) -> Option<Color> { self.ring }
+
+    /// Sets the colour of the ring. The `None` hides it.
+    pub fn set_ring(&mut self, ring: Option<Color>) { self.ring = ring }
+
+    /// Moves the focus by keys of the frame and forgets its slots.
+    pub(crate) fn end_frame(&mut self, input: &Input) {
+        if self.focused.is_some_and(|focused| focused >= self.stops.len()) {
+            self.focused = None;
+        }
+
+        for event in input.events() {
+            match event {
+                Event::KeyDown(Key::Tab) => {
+                    self.focused = self.tab(input.state().modifiers().shift)
+                }
+                Event::KeyDown(key) => match key {
+                    Key::Left | Key::Up => self.focused = self.arrow(true),
+                    Key::Right | Key::Down => self.focused = self.arrow(false),
+                    _ => (),
+                }
+                _ => (),
+            }
+        }
+
+        self.stops.clear();
+    }
+
+    /// Returns the next stop by Tab, or the previous one with the `back`.
+    fn tab(&self, back: bool) -> Option<usize> {
+        let mut order: Vec<usize> = (0..self.stops.len())
+            .filter(|&i| self.stops[i].is_reachable())
+            .collect();
+        order.sort_by_key(|&i| self.stops[i].index);
+
+        if back {
+            order.reverse();
+        }
+
+        let current = match self.focused.and_then(|f| order.iter().position(|&i| i == f)) {
+            Some(current) => current,
+            None => return order.first().copied().or(self.focused),
+        };
+
+        let group = self.stops[order[current]].group;
+
+        order
+            .iter()
+            .cycle()
+            .skip(current + 1)
+            .take(order.len() - 1)
+            .copied()
+            .find(|&i| group.is_none() || self.stops[i].group != group)
+            .or(self.focused)
+    }
+
+    /// Returns the previous or the next stop in the group of the focused one.
+    fn arrow(&self, back: bool) -> Option<usize> {
+        let focused = self.focused?;
+        let group = self.stops[focused].group;
+
+        if group.is_none() {
+            return Some(focused);
+        }
+
+        let in_group = |&i: &usize| self.stops[i].group == group && self.stops[i].is_reachable();
+
+        let found = if back {
+            (0..focused).rev().find(in_group)
+        } else {
+            (focused + 1..self.stops.len()).find(in_group)
+        };
+
+        found.or(Some(focused))
+    }
+}
+
+impl Default for Focus {
+    fn default() -> Self { Focus::new() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::Modifiers;
+
+    fn press(focus: &mut Focus, stops: &[TabStop], key: Key, shift: bool) {
+        stops.iter().for_each(|&stop| { focus.add(stop); });
+
+        let mut input = Input::new(100, 1);
+        input.set_modifiers(Modifiers { shift, ..Modifiers::default() });
+        input.push(Event::KeyDown(key));
+        focus.end_frame(&input);
+    }
+
+    #[test]
+    fn tab() {
+        let stops = [TabStop::new(0), TabStop::new(-1), TabStop::new(0), TabStop::new(1)];
+        let mut focus = Focus::new();
+
+        press(&mut focus, &stops, Key::Tab, false);
+        assert!(focus.is_focused(FocusSlot(0)));
+        press(&mut focus, &stops, Key::Tab, false);
+        assert!(focus.is_focused(FocusSlot(2)));
+        press(&mut focus, &stops, Key::Tab, false);
+        assert!(focus.is_focused(FocusSlot(3)));
+        press(&mut focus, &stops, Key::Tab, false);
+        assert!(focus.is_focused(FocusSlot(0)));
+        press(&mut focus, &stops, Key::Tab, true);
+        assert!(focus.is_focused(FocusSlot(3)));
+
+        // The index goes first
+        let stops = [TabStop::new(2), TabStop::new(1)];
+        let mut focus = Focus::new();
+        press(&mut focus, &stops, Key::Tab, false);
+        assert!(focus.is_focused(FocusSlot(1)));
+    }
+
+    #[test]
+    fn groups() {
+        let stops = [
+            TabStop::new(0).group(1),
+            TabStop::new(0).group(1),
+            TabStop::new(0).group(1),
+            TabStop::new(0),
+        ];
+        let mut focus = Focus::new();
+
+        press(&mut focus, &stops, Key::Tab, false);
+        press(&mut focus, &stops, Key::Down, false);
+        press(&mut focus, &stops, Key::Down, false);
+        assert!(focus.is_focused(FocusSlot(2)));
+        press(&mut focus, &stops, Key::Down, false);
+        assert!(focus.is_focused(FocusSlot(2)));
+
+        press(&mut focus, &stops, Key::Tab, false);
+        assert!(focus.is_focused(FocusSlot(3)));
+        press(&mut focus, &stops, Key::Left, false);
+        assert!(focus.is_focused(FocusSlot(3)));
+
+        // Tab skips the rest of the group
+        press(&mut focus, &stops, Key::Tab, true);
+        assert!(focus.is_focused(FocusSlot(2)));
+        press(&mut focus, &stops, Key::Tab, true);
+        assert!(focus.is_focused(FocusSlot(3)));
+    }
+}
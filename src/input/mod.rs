@@ -0,0 +1,7 @@
+mod event;
+mod focus;
+mod state;
+
+pub use event::*;
+pub use focus::{Focus, FocusSlot, TabStop};
+pub use state::{Input, InputState};
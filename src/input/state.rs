@@ -0,0 +1,316 @@
+use super::{
+    Event,
+    Key,
+    Modifiers,
+    MouseButton,
+};
+use crate::common::Vec2d;
+
+/// The snapshot of the input at the current frame.
+#[derive(Clone, Debug, Default)]
+pub struct InputState {
+    mouse: Option<Vec2d<i32>>,
+    buttons: Vec<MouseButton>,
+    keys: Vec<Key>,
+    modifiers: Modifiers,
+}
+
+#[allow(dead_code)]
+impl InputState {
+    /// The cursor position, or `None` if it's outside the window.
+    pub fn mouse(&self) -> Option<Vec2d<i32>> { self.mouse }
+
+    pub fn is_held(&self, button: MouseButton) -> bool { self.buttons.contains(&button) }
+
+    pub fn buttons(&self) -> &[MouseButton] { &self.buttons }
+
+    pub fn is_key_held(&self, key: Key) -> bool { self.keys.contains(&key) }
+
+    pub fn keys(&self) -> &[Key] { &self.keys }
+
+    pub fn modifiers(&self) -> Modifiers { self.modifiers }
+
+    fn apply(&mut self, event: &Event) {
+        fn press<T: PartialEq>(held: &mut Vec<T>, item: T) {
+            if !held.contains(&item) {
+                held.push(item)
+            }
+        }
+
+        match *event {
+            Event::MouseMove(pos) => self.mouse = Some(pos),
+            Event::MouseLeave => self.mouse = None,
+            Event::MouseDown(button, pos) => {
+                self.mouse = Some(pos);
+                press(&mut self.buttons, button);
+            }
+            Event::MouseUp(button, pos) => {
+                self.mouse = Some(pos);
+                self.buttons.retain(|&b| b != button);
+            }
+            Event::KeyDown(key) => press(&mut self.keys, key),
+            Event::KeyUp(key) => self.keys.retain(|&k| k != key),
+            Event::Scroll(_) | Event::Text(_) => (),
+        }
+    }
+}
+
+/// Where positions are mapped for the content that reads the input.
+#[derive(Copy, Clone, Debug)]
+enum Local {
+    Window,
+    /// The inverse of the content transform.
+    Inverse(glm::Mat4),
+    /// The transform collapses the content, so the pointer is never over it.
+    Collapsed,
+}
+
+/// Collects window events of a frame and translates them to UI coordinates.
+#[derive(Debug)]
+pub struct Input {
+    state: InputState,
+    events: Vec<Event>,
+    window_height: i32,
+    pixel_size: i32,
+    focused: bool,
+    local: Local,
+}
+
+#[allow(dead_code)]
+impl Input {
+    pub fn new(window_height: i32, pixel_size: i32) -> Self {
+        Input {
+            state: InputState::default(),
+            events: Vec::new(),
+            window_height,
+            pixel_size,
+            focused: true,
+            local: Local::Window,
+        }
+    }
+
+    /// The state with positions in UI pixels of the window, see `mouse`.
+    pub fn state(&self) -> &InputState { &self.state }
+
+    /// The cursor position in coordinates of the current transform.
+    pub fn mouse(&self) -> Option<Vec2d<i32>> { self.state.mouse.map(|pos| self.to_local(pos)) }
+
+    /// Events received since the previous frame, with positions in coordinates
+    /// of the current transform.
+    pub fn events(&self) -> impl Iterator<Item=Event> + '_ {
+        self.events.iter().map(move |event| event.map_pos(|pos| self.to_local(pos)))
+    }
+
+    /// Adds the event and updates the state. Events of the unfocused window are ignored,
+    /// since it doesn't draw frames that would take them.
+    pub fn push(&mut self, event: Event) {
+        if !self.focused {
+            return;
+        }
+
+        self.state.apply(&event);
+        self.events.push(event);
+    }
+
+    /// Sets held modifiers, the window does it when they are changed.
+    pub fn set_modifiers(&mut self, modifiers: Modifiers) { self.state.modifiers = modifiers }
+
+    pub(crate) fn resize(&mut self, window_height: i32) { self.window_height = window_height }
+
+    pub(crate) fn end_frame(&mut self) { self.events.clear() }
+
+    pub fn is_focused(&self) -> bool { self.focused }
+
+    /// Releases everything held when the window loses the focus, then ignores events until
+    /// it gets the focus back.
+    pub(crate) fn set_focused(&mut self, focused: bool) {
+        if self.focused && !focused {
+            self.release_all();
+        }
+
+        self.focused = focused;
+    }
+
+    /// Releases everything held, e.g. when the window loses focus and won't get the releases.
+    fn release_all(&mut self) {
+        let pos = self.state.mouse.unwrap_or_default();
+
+        for button in std::mem::take(&mut self.state.buttons) {
+            self.events.push(Event::MouseUp(button, pos));
+        }
+
+        for key in std::mem::take(&mut self.state.keys) {
+            self.events.push(Event::KeyUp(key));
+        }
+
+        self.state.modifiers = Modifiers::default();
+    }
+
+    /// Sets the transform of the content that reads the input. Positions of `events`
+    /// and `mouse` are mapped back through it, so they are in coordinates of the content.
+    pub(crate) fn set_transform(&mut self, transform: &glm::Mat4) {
+        self.local = if *transform == glm::Mat4::identity() {
+            Local::Window
+        } else {
+            transform.try_inverse().map_or(Local::Collapsed, Local::Inverse)
+        };
+    }
+
+    fn to_local(&self, pos: Vec2d<i32>) -> Vec2d<i32> {
+        match self.local {
+            Local::Window => pos,
+            Local::Inverse(inverse) => {
+                // Maps the center of the pixel, so float errors don't move it to the neighbour
+                let p = inverse * glm::vec4(pos.x as f32 + 0.5, pos.y as f32 + 0.5, 0.0, 1.0);
+                Vec2d::new(p.x.floor() as i32, p.y.floor() as i32)
+            }
+            Local::Collapsed => Vec2d::new(i32::MIN, i32::MIN),
+        }
+    }
+
+    /// Converts window pixels from the top left corner to UI pixels from the bottom left one.
+    pub fn to_ui(&self, (x, y): (f64, f64)) -> Vec2d<i32> {
+        let pixel_size = self.pixel_size as f64;
+        let flipped = (self.window_height - 1) as f64 - y.floor();
+
+        Vec2d::new(
+            (x.floor() / pixel_size).floor() as i32,
+            (flipped / pixel_size).floor() as i32,
+        )
+    }
+
+    /// Translates the window event. Returns `true` if it was an input event.
+    pub(crate) fn handle(&mut self, event: &glutin::event::WindowEvent) -> bool {
+        use glutin::event::{WindowEvent, ElementState, MouseScrollDelta, KeyboardInput};
+
+        // Pixel scroll of touchpads is converted to lines
+        const PIXELS_PER_LINE: f64 = 16.0;
+
+        let event = match *event {
+            WindowEvent::CursorMoved { position, .. } =>
+                Event::MouseMove(self.to_ui(position.into())),
+            WindowEvent::CursorLeft { .. } => Event::MouseLeave,
+            WindowEvent::MouseInput { state, button, .. } => {
+                let pos = self.state.mouse.unwrap_or_default();
+
+                match state {
+                    ElementState::Pressed => Event::MouseDown(button.into(), pos),
+                    ElementState::Released => Event::MouseUp(button.into(), pos),
+                }
+            }
+            WindowEvent::MouseWheel { delta, .. } => match delta {
+                MouseScrollDelta::LineDelta(x, y) => Event::Scroll(Vec2d::new(x, y)),
+                MouseScrollDelta::PixelDelta(pos) => Event::Scroll(Vec2d::new(
+                    (pos.x / PIXELS_PER_LINE) as f32,
+                    (pos.y / PIXELS_PER_LINE) as f32,
+                )),
+            }
+            WindowEvent::KeyboardInput {
+                input: KeyboardInput { state, virtual_keycode: Some(key), .. }, ..
+            } => match state {
+                ElementState::Pressed => Event::KeyDown(key),
+                ElementState::Released => Event::KeyUp(key),
+            }
+            WindowEvent::ReceivedCharacter(c) if !c.is_control() => Event::Text(c),
+            WindowEvent::ModifiersChanged(modifiers) => {
+                self.set_modifiers(modifiers.into());
+                return true;
+            }
+            _ => return false,
+        };
+
+        self.push(event);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn events(input: &Input) -> Vec<Event> { input.events().collect() }
+
+    #[test]
+    fn to_ui() {
+        // The window of 5 x 3 UI pixels
+        let input = Input::new(6, 2);
+
+        assert_eq!(input.to_ui((0.0, 0.0)), Vec2d::new(0, 2));
+        assert_eq!(input.to_ui((1.5, 1.9)), Vec2d::new(0, 2));
+        assert_eq!(input.to_ui((2.0, 2.0)), Vec2d::new(1, 1));
+        assert_eq!(input.to_ui((9.0, 5.0)), Vec2d::new(4, 0));
+        assert_eq!(input.to_ui((-1.0, 6.0)), Vec2d::new(-1, -1));
+    }
+
+    #[test]
+    fn state() {
+        let mut input = Input::new(10, 1);
+
+        input.push(Event::MouseMove(Vec2d::new(3, 4)));
+        input.push(Event::MouseDown(MouseButton::Left, Vec2d::new(3, 4)));
+        input.push(Event::KeyDown(Key::A));
+        assert_eq!(input.state().mouse(), Some(Vec2d::new(3, 4)));
+        assert!(input.state().is_held(MouseButton::Left));
+        assert!(input.state().is_key_held(Key::A));
+
+        input.end_frame();
+        assert!(events(&input).is_empty());
+        assert!(input.state().is_held(MouseButton::Left));
+
+        input.release_all();
+        assert!(!input.state().is_held(MouseButton::Left));
+        assert!(!input.state().is_key_held(Key::A));
+        assert_eq!(events(&input).len(), 2);
+
+        input.push(Event::MouseLeave);
+        assert_eq!(input.state().mouse(), None);
+    }
+
+    #[test]
+    fn unfocused() {
+        let mut input = Input::new(10, 1);
+        input.push(Event::MouseDown(MouseButton::Left, Vec2d::new(3, 4)));
+        input.end_frame();
+
+        input.set_focused(false);
+        assert_eq!(events(&input), [Event::MouseUp(MouseButton::Left, Vec2d::new(3, 4))]);
+
+        // The window draws the frame with releases, then nothing comes until the focus
+        input.end_frame();
+        input.push(Event::MouseMove(Vec2d::new(5, 5)));
+        input.push(Event::KeyDown(Key::A));
+        input.set_focused(false);
+        assert!(events(&input).is_empty());
+        assert!(!input.state().is_key_held(Key::A));
+
+        input.set_focused(true);
+        input.push(Event::KeyDown(Key::A));
+        assert_eq!(events(&input), [Event::KeyDown(Key::A)]);
+    }
+
+    #[test]
+    fn transformed() {
+        let mut input = Input::new(100, 1);
+        input.push(Event::MouseDown(MouseButton::Left, Vec2d::new(9, 4)));
+        input.push(Event::KeyDown(Key::A));
+
+        // Twice as large content from (1, 0)
+        let scale = glm::translation(&glm::vec3(1.0, 0.0, 0.0))
+            * glm::scaling(&glm::vec3(2.0, 2.0, 1.0));
+        input.set_transform(&scale);
+
+        assert_eq!(events(&input), [
+            Event::MouseDown(MouseButton::Left, Vec2d::new(4, 2)),
+            Event::KeyDown(Key::A),
+        ]);
+        assert_eq!(input.mouse(), Some(Vec2d::new(4, 2)));
+        assert_eq!(input.state().mouse(), Some(Vec2d::new(9, 4)));
+        assert!(input.state().is_held(MouseButton::Left));
+
+        input.set_transform(&glm::scaling(&glm::vec3(0.0, 1.0, 1.0)));
+        assert_eq!(input.mouse(), Some(Vec2d::new(i32::MIN, i32::MIN)));
+
+        input.set_transform(&glm::Mat4::identity());
+        assert_eq!(input.mouse(), Some(Vec2d::new(9, 4)));
+    }
+}
@@ -1,17 +1,27 @@
 mod common;
 mod graphics;
+mod input;
 mod ui;
 
-use common::{Rect, Color};
-use graphics::{Window, FontStyle};
+use std::{cell::Cell, rc::Rc};
+
+use common::{Rect, Color, Align, Vec2d, Anchor};
+use graphics::{Window, Assets, FontStyle, TextureAtlas};
+use input::{Event, InputState, MouseButton};
 use ui::*;
 
 fn main() {
-    let window = Window::new("App", (600, 400), 2)
+    let window = Window::new("App", (600, 400), 2, Assets::new())
         .with_bg(Color::rgb(0.15, 0.01, 0.06));
 
     let rect = Col::red(Rect::new((31, 31), (12, 12)));
-    let img = Pos::right_bot(4, 4, Image::new("./data/1.png", window.render()));
+    let img = Image::new("1.png", window.render()).unwrap();
+    let img = Margin::uniform(4, Pos::right_bot(0, 0, img));
+
+    let mut icons = TextureAtlas::builder((64, 64)).srgb(window.render().is_srgb());
+    let icon = icons.add_file("u.png", window.render().assets()).unwrap();
+    let icons = icons.build().unwrap();
+    let icon = Pos::left_bot(125, 4, Image::from_atlas(&icons, icon));
     let text = Col::green("\njie adding\nthe КАПС\nREADME.,!:;|*@\nПривет, мир ЪЁь!1230");
 
     let style = FontStyle::new().shadow((0, -1), Color::blue());
@@ -20,12 +30,125 @@ fn main() {
 
     let hello = Font::new("~~~~~~~~~~\nHello,\nthe\nworld!\n~~~~~~~~~~\nv.")
         .shadow((1, -1), Color::rgb(0.6, 0.0, 0.7));
+    let hello = Transform::translate((2.0, 0.0), hello);
+
+    let hud = Column::new()
+        .spacing(1)
+        .align(Align::End)
+        .with(Row::new().spacing(4).with(Col::red("HP")).with("100"))
+        .with(Row::new().spacing(4).with(Col::blue("MP")).with("25"));
+    let hud = Opacity::new(0.8, hud);
+    let hud = Pos::right_top(2, 2, Stack::new().with(hud));
+
+    let bar = Flex::row()
+        .size((120, 9))
+        .justify(Justify::SpaceBetween)
+        .align(CrossAlign::Center)
+        .with(Rotate::new(90.0, Col::red("F1")))
+        .with_item(FlexItem::new().grow(1.0), Pos::center("Menu"))
+        .with(Col::red("F2"));
+    let bar = Pos::bot(2, bar);
+
+    let settings = Grid::new()
+        .columns(vec![Track::Auto, Track::Fixed(20)])
+        .rows(vec![Track::Auto, Track::Auto])
+        .gap((4, 1))
+        .cell(0, 0, "Sound")
+        .cell(0, 1, Col::green("On"))
+        .with(GridCell::new(1, 0).span(1, 2), Col::blue("Volume 80%"));
+    let settings = Pos::left_bot(2, 2, settings);
+
+    let panel = Stack::new()
+        .with(Col::new("#4d1a33".parse().unwrap(), Rect::new((0, 0), (40, 20))))
+        .with(Pos::right_top(0, 0, Padding::uniform(1, Col::red("x"))))
+        .with(Pos::left_bot(0, 0, Padding::symmetric(2, 1, "Panel with a long title")));
+    let panel = Frame::new(Area::Size(Vec2d::new(40, 20)), Clip::new(panel));
+    let panel = Pos::right_bot(4, 40, panel);
+    let panel = Id::new(1, panel);
+
+    // The title center is placed at the third of the window width
+    let title = Anchor::new((1.0 / 3.0, 1.0), (0.5, 1.0)).pad((0, -2));
+    let gold = Col::new(Color::from_name("gold").unwrap(), "Title");
+    let title = Pos::new(title.into(), Scale::new(2.0, gold).pivot((0.5, 1.0)));
+
+    let played = Rc::new(Cell::new(0));
+    let counter = Rc::clone(&played);
+    let hovered_style = ButtonStyle::new(Color::from_name("darkorange").unwrap(), Color::black());
+
+    let menu = Column::new()
+        .spacing(2)
+        .with(Button::new("Play")
+            .size((30, 11))
+            .style(ButtonState::Hovered, hovered_style)
+            .focus_group(1)
+            .on_click(move || counter.set(counter.get() + 1)))
+        .with(Button::new("Quit").size((30, 11)).focus_group(1).disabled(true));
+    let menu = Pos::left(4, menu);
+
+    let name = TextInput::new(60).with_text("Player");
+    let notes = TextInput::multiline(60, 3).with_text("Notes\nabout the\nlevel\n...");
+    let form = Pos::left_top(2, 80, Column::new().spacing(2).with(name).with(notes));
+
+    let music = Checkbox::new("Music").checked(true);
+    let difficulty = RadioGroup::new().with("Easy").with("Normal").with("Hard").select(1);
+    let volume = Slider::int(0, 100).with_value(80.0);
+    let loading = ProgressBar::new((50, 3));
+
+    let mut list = Column::new().spacing(1).align(Align::Start);
+    for i in 1..=20 {
+        list.push(format!("Item {}", i));
+    }
+    let list = Pos::left_bot(70, 4, Scroll::new((50, 40), list));
+
+    let mut clicks = 0;
 
     window.run(move |render| {
+        clicks += render
+            .input()
+            .events()
+            .filter(|event| matches!(event, Event::MouseDown(MouseButton::Left, _)))
+            .count();
+
+        let cursor = cursor_text(render.input().state(), clicks);
+        let cursor = format!("{} played {} calls {}", cursor, played.get(), render.draw_calls());
+
+        let hovered = render
+            .input()
+            .state()
+            .mouse()
+            .and_then(|pos| render.hit_test(&panel).id_at(pos));
+
         render.draw(&rect);
         render.draw(&img);
+        render.draw(&icon);
         render.draw(&Pos::left_top(0, 0, &text));
         render.draw(&hello);
         render.draw(&cyr);
+        render.draw(&hud);
+        render.draw(&bar);
+        render.draw(&settings);
+        render.draw(&Opacity::new(if hovered.is_some() { 1.0 } else { 0.7 }, &panel));
+        render.draw(&title);
+        render.draw(&menu);
+        render.draw(&form);
+        render.draw(&list);
+
+        loading.set_value(volume.value() / 100.0);
+
+        let options = Column::new()
+            .spacing(2)
+            .with(&music)
+            .with(&difficulty)
+            .with(&volume)
+            .with(&loading);
+        render.draw(&Pos::right_top(4, 30, options));
+        render.draw(&Pos::left_top(2, 50, Col::blue(cursor)));
     });
 }
+
+fn cursor_text(state: &InputState, clicks: usize) -> String {
+    match state.mouse() {
+        Some(pos) => format!("{}, {} ({})", pos.x, pos.y, clicks),
+        None => format!("({})", clicks),
+    }
+}
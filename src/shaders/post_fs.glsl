@@ -1,11 +1,23 @@
 #version 330 core
 
 uniform sampler2D frame;
+uniform bool srgb;
 
 in vec2 st_fs;
 
 out vec4 frag;
 
+vec3 linear_to_srgb(vec3 c) {
+	vec3 low = c * 12.92;
+	vec3 high = 1.055 * pow(c, vec3(1.0 / 2.4)) - 0.055;
+
+	return mix(low, high, step(vec3(0.0031308), c));
+}
+
 void main() {
 	frag = texture(frame, st_fs);
+
+	if (srgb) {
+		frag.rgb = linear_to_srgb(frag.rgb);
+	}
 }
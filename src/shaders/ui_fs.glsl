@@ -1,17 +1,17 @@
 #version 330 core
 
-uniform vec4 col;
 uniform sampler2D texture0;
 uniform bool draw_texture;
 
 in vec2 st_fs;
+in vec4 col_fs;
 
 out vec4 frag;
 
 void main() {
     if (draw_texture) {
-        frag = texture(texture0, st_fs) * col;
+        frag = texture(texture0, st_fs) * col_fs;
     } else {
-        frag = col;
+        frag = col_fs;
     }
 }
@@ -2,12 +2,16 @@
 
 layout (location = 0) in vec2 pos;
 layout (location = 1) in vec2 st;
+layout (location = 2) in vec4 col;
 
 uniform mat4 projection;
+uniform mat4 model;
 
 out vec2 st_fs;
+out vec4 col_fs;
 
 void main() {
     st_fs = st;
-    gl_Position = projection * vec4(pos, 0.0, 1.0);
+    col_fs = col;
+    gl_Position = projection * model * vec4(pos, 0.0, 1.0);
 }
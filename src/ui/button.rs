@@ -0,0 +1,373 @@
+use std::cell::{Cell, RefCell};
+
+use super::{
+    focus::Focusable,
+    press::{self, Press},
+    Image,
+};
+use crate::{
+    common::{
+        Color,
+        Insets,
+        Position,
+        Rect,
+        Vec2d,
+    },
+    graphics::{
+        Render,
+        Draw,
+        DrawParameters,
+        Metrics,
+    },
+    input::{Input, TabStop},
+};
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ButtonState {
+    Normal,
+    Hovered,
+    Pressed,
+    Disabled,
+}
+
+impl ButtonState {
+    fn index(self) -> usize { self as usize }
+}
+
+/// The look of the button in some state.
+#[derive(Copy, Clone, Debug)]
+pub struct ButtonStyle<'a> {
+    pub background: Color,
+    pub text: Color,
+    /// The image drawn in the center, over the background.
+    pub image: Option<&'a Image>,
+}
+
+#[allow(dead_code)]
+impl<'a> ButtonStyle<'a> {
+    pub fn new(background: Color, text: Color) -> Self {
+        ButtonStyle {
+            background,
+            text,
+            image: None,
+        }
+    }
+
+    pub fn image(mut self, image: &'a Image) -> Self {
+        self.image = Some(image);
+        self
+    }
+}
+
+/// A clickable box around the content.
+///
+/// It reads the input of the `Render` while drawing, so it must be drawn every frame.
+/// The click happens when the left button is pressed and released over the button,
+/// or when Enter or Space is pressed while the button is focused.
+pub struct Button<'a, U> {
+    ui: U,
+    padding: Insets<i32>,
+    size: Option<Vec2d<i32>>,
+    styles: [ButtonStyle<'a>; 4],
+    disabled: bool,
+    state: Cell<ButtonState>,
+    press: Press,
+    focus: Focusable,
+    clicked: Cell<bool>,
+    on_click: Option<RefCell<Box<dyn FnMut() + 'a>>>,
+}
+
+#[allow(dead_code)]
+impl<'a, U> Button<'a, U>
+    where
+        U: Draw,
+{
+    pub fn new(ui: U) -> Self {
+        let gray = |v| Color::rgb(v, v, v);
+
+        Button {
+            ui,
+            padding: Insets::symmetric(2, 1),
+            size: None,
+            styles: [
+                ButtonStyle::new(gray(0.3), Color::white()),
+                ButtonStyle::new(gray(0.4), Color::white()),
+                ButtonStyle::new(gray(0.2), Color::white()),
+                ButtonStyle::new(gray(0.2), gray(0.5)),
+            ],
+            disabled: false,
+            state: Cell::new(ButtonState::Normal),
+            press: Press::default(),
+            focus: Focusable::default(),
+            clicked: Cell::new(false),
+            on_click: None,
+        }
+    }
+
+    pub fn padding<I>(mut self, padding: I) -> Self
+        where
+            I: Into<Insets<i32>>,
+    {
+        self.padding = padding.into();
+        self
+    }
+
+    /// Sets the fixed size. By default the button fits the content.
+    pub fn size<S>(mut self, size: S) -> Self
+        where
+            S: Into<Vec2d<i32>>,
+    {
+        self.size = Some(size.into());
+        self
+    }
+
+    pub fn style(mut self, state: ButtonState, style: ButtonStyle<'a>) -> Self {
+        self.styles[state.index()] = style;
+        self
+    }
+
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.set_disabled(disabled);
+        self
+    }
+
+    pub fn on_click<F>(mut self, f: F) -> Self
+        where
+            F: FnMut() + 'a,
+    {
+        self.on_click = Some(RefCell::new(Box::new(f)));
+        self
+    }
+
+    /// Sets the order of Tab stops. Negative buttons are skipped by Tab.
+    pub fn tab_index(mut self, index: i32) -> Self {
+        self.focus.set_stop(TabStop { index, ..self.focus.stop() });
+        self
+    }
+
+    /// Puts the button in the group where arrows move the focus.
+    pub fn focus_group(mut self, group: u32) -> Self {
+        self.focus.set_stop(self.focus.stop().group(group));
+        self
+    }
+
+    pub fn set_disabled(&mut self, disabled: bool) {
+        self.disabled = disabled;
+        self.press.reset();
+    }
+
+    pub fn is_disabled(&self) -> bool { self.disabled }
+
+    /// The state at the last drawing.
+    pub fn state(&self) -> ButtonState { self.state.get() }
+
+    /// Whether the button was clicked at the last drawing.
+    pub fn clicked(&self) -> bool { self.clicked.get() }
+
+    pub fn is_focused(&self) -> bool { self.focus.is_focused() }
+
+    pub fn set_focused(&self, focused: bool) { self.focus.request(focused) }
+
+    /// Updates the state from the input, when the button occupies the `rect`.
+    fn update(&self, input: &Input, rect: Rect<i32>) {
+        self.clicked.set(false);
+
+        if self.disabled {
+            self.state.set(ButtonState::Disabled);
+            self.focus.set(false);
+            return;
+        }
+
+        self.focus.click(input, rect);
+        self.clicked.set(self.press.update(input, rect) | self.focus.activated(input));
+
+        self.state.set(match (press::is_hovered(input, rect), self.press.is_pressed()) {
+            (true, true) => ButtonState::Pressed,
+            (true, false) => ButtonState::Hovered,
+            _ => ButtonState::Normal,
+        });
+
+        if self.clicked.get() {
+            if let Some(f) = &self.on_click {
+                (f.borrow_mut())();
+            }
+        }
+    }
+}
+
+impl<U> Draw for Button<'_, U>
+    where
+        U: Draw,
+{
+    fn draw(&self, render: &mut Render, mut params: DrawParameters) {
+        let rect = params.render_rect(self.measure(render.metrics(), &params));
+
+        let slot = self.focus.begin(render, !self.disabled);
+        self.update(render.input(), rect);
+        let style = self.styles[self.state().index()];
+
+        render.fill_rect(rect, style.background);
+
+        params.frame = rect;
+        params.position = Position::Center;
+
+        if let Some(image) = style.image {
+            image.draw(render, params);
+        }
+
+        params.frame = rect.inset(self.padding);
+        params.color = style.text;
+        self.ui.draw(render, params);
+
+        self.focus.end(render, slot, rect);
+    }
+
+    fn measure(&self, metrics: &Metrics, params: &DrawParameters) -> Vec2d<i32> {
+        if let Some(size) = self.size {
+            return size;
+        }
+
+        let content = self.ui.measure(metrics, params) + self.padding.size();
+
+        self.styles
+            .iter()
+            .filter_map(|style| style.image)
+            .fold(content, |size, image| size.max(image.measure(metrics, params)))
+    }
+}
+
+impl<U> std::fmt::Debug for Button<'_, U>
+    where
+        U: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Button")
+            .field("ui", &self.ui)
+            .field("padding", &self.padding)
+            .field("size", &self.size)
+            .field("disabled", &self.disabled)
+            .field("state", &self.state.get())
+            .finish()
+    }
+}
+
+impl<U> std::ops::Deref for Button<'_, U> {
+    type Target = U;
+
+    fn deref(&self) -> &Self::Target { &self.ui }
+}
+
+impl<U> std::ops::DerefMut for Button<'_, U> {
+    fn deref_mut(&mut self) -> &mut Self::Target { &mut self.ui }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::{Event, Key, MouseButton};
+
+    fn rect() -> Rect<i32> { Rect::new((10, 10), (20, 10)) }
+
+    fn frame(button: &Button<&str>, events: &[Event]) -> Input {
+        let mut input = Input::new(100, 1);
+        events.iter().for_each(|&event| input.push(event));
+
+        button.update(&input, rect());
+        input
+    }
+
+    #[test]
+    fn click() {
+        let clicks = Cell::new(0);
+        let button = Button::new("Ok").on_click(|| clicks.set(clicks.get() + 1));
+
+        let inside = Vec2d::new(15, 15);
+        let outside = Vec2d::new(5, 5);
+
+        frame(&button, &[Event::MouseMove(inside)]);
+        assert_eq!(button.state(), ButtonState::Hovered);
+
+        frame(&button, &[
+            Event::MouseMove(inside),
+            Event::MouseDown(MouseButton::Left, inside),
+        ]);
+        assert_eq!(button.state(), ButtonState::Pressed);
+        assert!(!button.clicked());
+
+        frame(&button, &[Event::MouseUp(MouseButton::Left, inside)]);
+        assert!(button.clicked());
+        assert_eq!(clicks.get(), 1);
+
+        // Pressed outside
+        frame(&button, &[
+            Event::MouseDown(MouseButton::Left, outside),
+            Event::MouseUp(MouseButton::Left, inside),
+        ]);
+        assert!(!button.clicked());
+
+        // Released outside
+        frame(&button, &[
+            Event::MouseDown(MouseButton::Left, inside),
+            Event::MouseUp(MouseButton::Left, outside),
+        ]);
+        assert!(!button.clicked());
+        assert_eq!(button.state(), ButtonState::Normal);
+        assert_eq!(clicks.get(), 1);
+    }
+
+    #[test]
+    fn scaled() {
+        let button = Button::new("Ok");
+
+        // Drawn twice as large from the bottom left corner, so (10, 10, 40, 20)
+        let scale = crate::graphics::around((10.0, 10.0), &glm::scaling(&glm::vec3(2.0, 2.0, 1.0)));
+        let drawn = Vec2d::new(45, 25);
+
+        let transformed = |events: &[Event]| {
+            let mut input = Input::new(100, 1);
+            events.iter().for_each(|&event| input.push(event));
+            input.set_transform(&scale);
+            button.update(&input, rect());
+        };
+
+        transformed(&[Event::MouseDown(MouseButton::Left, drawn)]);
+        assert_eq!(button.state(), ButtonState::Pressed);
+        transformed(&[Event::MouseUp(MouseButton::Left, drawn)]);
+        assert!(button.clicked());
+
+        // Outside of the untransformed rect
+        frame(&button, &[
+            Event::MouseDown(MouseButton::Left, drawn),
+            Event::MouseUp(MouseButton::Left, drawn),
+        ]);
+        assert!(!button.clicked());
+    }
+
+    #[test]
+    fn keys() {
+        let button = Button::new("Ok");
+
+        frame(&button, &[Event::KeyDown(Key::Return)]);
+        assert!(!button.clicked());
+
+        button.set_focused(true);
+        frame(&button, &[Event::KeyDown(Key::Space)]);
+        assert!(button.clicked());
+
+        frame(&button, &[Event::MouseDown(MouseButton::Left, Vec2d::new(5, 5))]);
+        assert!(!button.is_focused());
+    }
+
+    #[test]
+    fn disabled() {
+        let button = Button::new("Ok").disabled(true);
+        let inside = Vec2d::new(15, 15);
+
+        frame(&button, &[
+            Event::MouseDown(MouseButton::Left, inside),
+            Event::MouseUp(MouseButton::Left, inside),
+        ]);
+        assert!(!button.clicked());
+        assert_eq!(button.state(), ButtonState::Disabled);
+    }
+}
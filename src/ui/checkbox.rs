@@ -0,0 +1,227 @@
+use std::cell::Cell;
+
+use super::{
+    focus::Focusable,
+    press::{self, Press},
+    Image,
+};
+use crate::{
+    common::{
+        Color,
+        Position,
+        Rect,
+        Vec2d,
+    },
+    graphics::{
+        Render,
+        Draw,
+        DrawParameters,
+        Metrics,
+    },
+    input::{Input, TabStop},
+};
+
+/// The box that is toggled by a click, with the label to the right.
+/// The check mark has the inherited colour. The focused box is toggled by Enter or Space.
+#[derive(Debug)]
+pub struct Checkbox<'a, U> {
+    ui: U,
+    size: i32,
+    label_gap: i32,
+    skin: Option<(&'a Image, &'a Image)>,
+    checked: Cell<bool>,
+    changed: Cell<bool>,
+    press: Press,
+    focus: Focusable,
+}
+
+#[allow(dead_code)]
+impl<'a, U> Checkbox<'a, U>
+    where
+        U: Draw,
+{
+    pub fn new(ui: U) -> Self {
+        Checkbox {
+            ui,
+            size: 7,
+            label_gap: 2,
+            skin: None,
+            checked: Cell::new(false),
+            changed: Cell::new(false),
+            press: Press::default(),
+            focus: Focusable::default(),
+        }
+    }
+
+    pub fn checked(self, checked: bool) -> Self {
+        self.checked.set(checked);
+        self
+    }
+
+    /// Sets the side of the box.
+    pub fn size(mut self, size: i32) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Sets the space between the box and the label.
+    pub fn label_gap(mut self, gap: i32) -> Self {
+        self.label_gap = gap;
+        self
+    }
+
+    /// Draws images instead of the box.
+    pub fn skin(mut self, unchecked: &'a Image, checked: &'a Image) -> Self {
+        self.skin = Some((unchecked, checked));
+        self
+    }
+
+    /// Sets the order of Tab stops. Negative boxes are skipped by Tab.
+    pub fn tab_index(mut self, index: i32) -> Self {
+        self.focus.set_stop(TabStop { index, ..self.focus.stop() });
+        self
+    }
+
+    /// Puts the box in the group where arrows move the focus.
+    pub fn focus_group(mut self, group: u32) -> Self {
+        self.focus.set_stop(self.focus.stop().group(group));
+        self
+    }
+
+    pub fn is_checked(&self) -> bool { self.checked.get() }
+
+    pub fn set_checked(&self, checked: bool) { self.checked.set(checked) }
+
+    /// Whether the box was toggled at the last drawing.
+    pub fn changed(&self) -> bool { self.changed.get() }
+
+    pub fn is_focused(&self) -> bool { self.focus.is_focused() }
+
+    pub fn set_focused(&self, focused: bool) { self.focus.request(focused) }
+
+    /// Toggles the box by the input, when it occupies the `rect`.
+    fn update(&self, input: &Input, rect: Rect<i32>) {
+        self.focus.click(input, rect);
+
+        let clicked = self.press.update(input, rect) | self.focus.activated(input);
+        if clicked {
+            self.checked.set(!self.checked.get());
+        }
+
+        self.changed.set(clicked);
+    }
+}
+
+impl<U> Draw for Checkbox<'_, U>
+    where
+        U: Draw,
+{
+    fn draw(&self, render: &mut Render, mut params: DrawParameters) {
+        let rect = params.render_rect(self.measure(render.metrics(), &params));
+
+        let slot = self.focus.begin(render, true);
+        self.update(render.input(), rect);
+
+        let hovered = press::is_hovered(render.input(), rect);
+        draw_mark(render, &params, self.skin, rect, self.size, hovered, self.is_checked());
+
+        let label = self.size + self.label_gap;
+        params.frame = Rect::new((rect.x + label, rect.y), (rect.width - label, rect.height));
+        params.position = Position::Left(0);
+        self.ui.draw(render, params);
+
+        self.focus.end(render, slot, rect);
+    }
+
+    fn measure(&self, metrics: &Metrics, params: &DrawParameters) -> Vec2d<i32> {
+        let label = self.ui.measure(metrics, params);
+        Vec2d::new(self.size + self.label_gap + label.x, self.size.max(label.y))
+    }
+}
+
+/// Draws the mark of the size at the left side of the rect, as checkboxes and radio buttons do.
+pub(super) fn draw_mark(
+    render: &mut Render,
+    params: &DrawParameters,
+    skin: Option<(&Image, &Image)>,
+    rect: Rect<i32>,
+    size: i32,
+    hovered: bool,
+    on: bool,
+) {
+    let mark = Rect::new((rect.x, rect.y + (rect.height - size) / 2), (size, size));
+
+    match skin {
+        Some((off_image, on_image)) => {
+            let image = if on { on_image } else { off_image };
+            image.draw_stretched(render, params.color, mark);
+        }
+        None => {
+            let background = if hovered { 0.35 } else { 0.25 };
+            render.fill_rect(mark, Color::rgb(background, background, background));
+
+            if on {
+                render.fill_rect(mark.inset(2), params.color);
+            }
+        }
+    }
+}
+
+impl<U> std::ops::Deref for Checkbox<'_, U> {
+    type Target = U;
+
+    fn deref(&self) -> &Self::Target { &self.ui }
+}
+
+impl<U> std::ops::DerefMut for Checkbox<'_, U> {
+    fn deref_mut(&mut self) -> &mut Self::Target { &mut self.ui }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::{Event, Key, MouseButton};
+
+    fn rect() -> Rect<i32> { Rect::new((10, 10), (20, 7)) }
+
+    fn frame(checkbox: &Checkbox<&str>, events: &[Event]) {
+        let mut input = Input::new(100, 1);
+        events.iter().for_each(|&event| input.push(event));
+
+        checkbox.update(&input, rect());
+    }
+
+    #[test]
+    fn toggle() {
+        let checkbox = Checkbox::new("Sound");
+        let inside = Vec2d::new(12, 12);
+
+        frame(&checkbox, &[Event::MouseDown(MouseButton::Left, inside)]);
+        frame(&checkbox, &[Event::MouseUp(MouseButton::Left, inside)]);
+        assert!(checkbox.is_checked());
+        assert!(checkbox.changed());
+
+        frame(&checkbox, &[]);
+        assert!(checkbox.is_checked());
+        assert!(!checkbox.changed());
+
+        // The click has focused the box
+        frame(&checkbox, &[Event::KeyDown(Key::Space)]);
+        assert!(!checkbox.is_checked());
+        assert!(checkbox.changed());
+
+        // Released outside
+        let outside = Vec2d::new(5, 5);
+        frame(&checkbox, &[Event::MouseDown(MouseButton::Left, inside)]);
+        frame(&checkbox, &[Event::MouseUp(MouseButton::Left, outside)]);
+        assert!(!checkbox.is_checked());
+    }
+
+    #[test]
+    fn label_gap() {
+        let params = DrawParameters::new(Rect::new((0, 0), (100, 50)));
+        let checkbox = Checkbox::new("ab").label_gap(3);
+
+        assert_eq!(checkbox.measure(&Metrics::test(), &params), Vec2d::new(20, 7));
+    }
+}
@@ -0,0 +1,59 @@
+use crate::{
+    common::Vec2d,
+    graphics::{
+        Render,
+        Draw,
+        DrawParameters,
+        Metrics,
+        HitTest,
+    },
+};
+
+/// Boxed children of a container, drawn in the order they were pushed.
+pub(super) struct Children<'a>(Vec<Box<dyn Draw + 'a>>);
+
+impl<'a> Children<'a> {
+    pub fn new() -> Self { Children(vec![]) }
+
+    pub fn push<D>(&mut self, child: D)
+        where
+            D: Draw + 'a,
+    { self.0.push(Box::new(child)) }
+
+    pub fn len(&self) -> usize { self.0.len() }
+
+    pub fn is_empty(&self) -> bool { self.0.is_empty() }
+
+    /// Measures every child with the same parameters.
+    pub fn sizes(&self, metrics: &Metrics, params: &DrawParameters) -> Vec<Vec2d<i32>> {
+        self.0
+            .iter()
+            .map(|child| child.measure(metrics, params))
+            .collect()
+    }
+
+    /// Draws each child with its parameters.
+    pub fn draw<I>(&self, render: &mut Render, params: I)
+        where
+            I: IntoIterator<Item=DrawParameters>,
+    {
+        for (child, params) in self.0.iter().zip(params) {
+            child.draw(render, params);
+        }
+    }
+
+    /// Passes the test to each child with its parameters.
+    pub fn hit<I>(&self, hits: &mut HitTest, params: I)
+        where
+            I: IntoIterator<Item=DrawParameters>,
+    {
+        for (child, params) in self.0.iter().zip(params) {
+            child.hit(hits, params);
+        }
+    }
+}
+
+/// Shows the number of children, since they aren't `Debug`.
+impl std::fmt::Debug for Children<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { self.len().fmt(f) }
+}
@@ -0,0 +1,56 @@
+use crate::{
+    common::Vec2d,
+    graphics::{
+        Render,
+        Draw,
+        DrawParameters,
+        Metrics,
+        HitTest,
+    },
+};
+
+/// Cuts off the content outside of the frame it's placed in, so it doesn't bleed out.
+/// Usually it's placed in the `Frame`.
+#[derive(Debug)]
+pub struct Clip<U> {
+    ui: U,
+}
+
+#[allow(dead_code)]
+impl<U> Clip<U>
+    where
+        U: Draw,
+{
+    pub fn new(ui: U) -> Self { Clip { ui } }
+}
+
+impl<U> Draw for Clip<U>
+    where
+        U: Draw,
+{
+    fn draw(&self, render: &mut Render, params: DrawParameters) {
+        render.push_clip(params.frame);
+        self.ui.draw(render, params);
+        render.pop_clip();
+    }
+
+    fn measure(&self, metrics: &Metrics, params: &DrawParameters) -> Vec2d<i32> {
+        self.ui.measure(metrics, params)
+    }
+
+    fn hit(&self, hits: &mut HitTest, params: DrawParameters) {
+        hits.push_clip(params.frame);
+        self.ui.hit(hits, params);
+        hits.pop_clip();
+    }
+}
+
+impl<U> std::ops::Deref for Clip<U> {
+    type Target = U;
+
+    fn deref(&self) -> &Self::Target { &self.ui }
+}
+
+impl<U> std::ops::DerefMut for Clip<U> {
+    fn deref_mut(&mut self) -> &mut Self::Target { &mut self.ui }
+}
@@ -1,15 +1,25 @@
 use crate::{
-    common::Color,
+    common::{
+        Color,
+        Vec2d,
+    },
     graphics::{
         Render,
         Draw,
         DrawParameters,
+        Metrics,
+        HitTest,
     },
 };
 
+/// Sets the colour of the content.
+///
+/// By default the colour replaces the inherited one. The tinting `Col` multiplies
+/// the inherited colour instead, so a semi-transparent parent fades its children.
 #[derive(Debug)]
 pub struct Col<U> {
     color: Color,
+    tint: bool,
     ui: U,
 }
 
@@ -18,7 +28,9 @@ impl<U> Col<U>
     where
         U: Draw,
 {
-    pub fn new(color: Color, ui: U) -> Self { Col { color, ui } }
+    pub fn new(color: Color, ui: U) -> Self { Col { color, tint: false, ui } }
+
+    pub fn tint(color: Color, ui: U) -> Self { Col { color, tint: true, ui } }
 
     pub fn black(ui: U) -> Self { Col::new(Color::black(), ui) }
     pub fn white(ui: U) -> Self { Col::new(Color::white(), ui) }
@@ -32,9 +44,17 @@ impl<U> Draw for Col<U>
         U: Draw,
 {
     fn draw(&self, render: &mut Render, mut params: DrawParameters) {
-        params.color = self.color;
+        params.color = if self.tint { params.color * self.color } else { self.color };
         self.ui.draw(render, params);
     }
+
+    fn measure(&self, metrics: &Metrics, params: &DrawParameters) -> Vec2d<i32> {
+        self.ui.measure(metrics, params)
+    }
+
+    fn hit(&self, hits: &mut HitTest, params: DrawParameters) {
+        self.ui.hit(hits, params);
+    }
 }
 
 impl<U> std::ops::Deref for Col<U> {
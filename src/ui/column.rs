@@ -0,0 +1,180 @@
+use super::children::Children;
+use crate::{
+    common::{
+        Align,
+        Position,
+        Rect,
+        Vec2d,
+    },
+    graphics::{
+        Render,
+        Draw,
+        DrawParameters,
+        Metrics,
+        HitTest,
+    },
+};
+
+/// Places children from top to bottom.
+#[derive(Debug)]
+pub struct Column<'a> {
+    spacing: i32,
+    align: Align,
+    children: Children<'a>,
+}
+
+#[allow(dead_code)]
+impl<'a> Column<'a> {
+    pub fn new() -> Self {
+        Column {
+            spacing: 0,
+            align: Align::default(),
+            children: Children::new(),
+        }
+    }
+
+    pub fn spacing(mut self, spacing: i32) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    /// Sets the horizontal alignment of children.
+    pub fn align(mut self, align: Align) -> Self {
+        self.align = align;
+        self
+    }
+
+    pub fn with<D>(mut self, child: D) -> Self
+        where
+            D: Draw + 'a,
+    {
+        self.children.push(child);
+        self
+    }
+
+    pub fn push<D>(&mut self, child: D)
+        where
+            D: Draw + 'a,
+    { self.children.push(child) }
+
+    pub fn len(&self) -> usize { self.children.len() }
+
+    pub fn is_empty(&self) -> bool { self.children.is_empty() }
+
+    fn child_position(&self) -> Position {
+        match self.align {
+            Align::Start => Position::Left(0),
+            Align::Center => Position::Center,
+            Align::End => Position::Right(0),
+        }
+    }
+
+    fn sizes(&self, metrics: &Metrics, params: &DrawParameters) -> (Vec<Vec2d<i32>>, Vec2d<i32>) {
+        let sizes = self.children.sizes(metrics, params);
+        let total = total_size(&sizes, self.spacing);
+
+        (sizes, total)
+    }
+
+    fn child_params(&self, metrics: &Metrics, params: DrawParameters) -> Vec<DrawParameters> {
+        let (sizes, total) = self.sizes(metrics, &params);
+        let rect = params.render_rect(total);
+
+        let mut child_params = params;
+        child_params.position = self.child_position();
+
+        frames(rect, &sizes, self.spacing)
+            .into_iter()
+            .map(|frame| DrawParameters { frame, ..child_params })
+            .collect()
+    }
+}
+
+/// The size of items placed from top to bottom.
+pub(super) fn total_size(sizes: &[Vec2d<i32>], spacing: i32) -> Vec2d<i32> {
+    let spaces = (sizes.len() as i32 - 1).max(0);
+
+    sizes
+        .iter()
+        .fold(Vec2d::new(0, spaces * spacing), |total, size| {
+            Vec2d::new(total.x.max(size.x), total.y + size.y)
+        })
+}
+
+/// Frames of items placed from top to bottom in the rect. Each one is as wide as the rect.
+pub(super) fn frames(rect: Rect<i32>, sizes: &[Vec2d<i32>], spacing: i32) -> Vec<Rect<i32>> {
+    let mut top = rect.top();
+
+    sizes
+        .iter()
+        .map(|size| {
+            let frame = Rect::new((rect.x, top - size.y), (rect.width, size.y));
+            top -= size.y + spacing;
+            frame
+        })
+        .collect()
+}
+
+impl Default for Column<'_> {
+    fn default() -> Self { Column::new() }
+}
+
+impl Draw for Column<'_> {
+    fn draw(&self, render: &mut Render, params: DrawParameters) {
+        let children = self.child_params(render.metrics(), params);
+        self.children.draw(render, children);
+    }
+
+    fn measure(&self, metrics: &Metrics, params: &DrawParameters) -> Vec2d<i32> {
+        self.sizes(metrics, params).1
+    }
+
+    fn hit(&self, hits: &mut HitTest, params: DrawParameters) {
+        let children = self.child_params(hits.metrics(), params);
+        self.children.hit(hits, children);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::Pos;
+
+    fn column() -> Column<'static> {
+        Column::new()
+            .spacing(2)
+            .with(Rect::new((0, 0), (20, 10)))
+            .with("a")
+            .with(Rect::new((0, 0), (3, 3)))
+    }
+
+    fn rects(column: Column) -> Vec<Rect<i32>> {
+        let ui = Pos::left_bot(0, 0, column);
+
+        HitTest::run(&Metrics::test(), &ui, Rect::new((0, 0), (100, 50)))
+            .iter()
+            .map(|hit| hit.rect)
+            .collect()
+    }
+
+    #[test]
+    fn spacing() {
+        let params = DrawParameters::new(Rect::new((0, 0), (100, 50)));
+        assert_eq!(column().measure(&Metrics::test(), &params), Vec2d::new(20, 23));
+
+        assert_eq!(rects(column()), vec![
+            Rect::new((0, 13), (20, 10)),
+            Rect::new((0, 5), (5, 6)),
+            Rect::new((0, 0), (3, 3)),
+        ]);
+    }
+
+    #[test]
+    fn align() {
+        let text = |align| rects(column().align(align))[1];
+
+        assert_eq!(text(Align::Start), Rect::new((0, 5), (5, 6)));
+        assert_eq!(text(Align::Center), Rect::new((8, 5), (5, 6)));
+        assert_eq!(text(Align::End), Rect::new((15, 5), (5, 6)));
+    }
+}
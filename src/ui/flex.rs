@@ -0,0 +1,677 @@
+use crate::{
+    common::{
+        Align,
+        Position,
+        Rect,
+        Vec2d,
+    },
+    graphics::{
+        Render,
+        Draw,
+        DrawParameters,
+        Metrics,
+        HitTest,
+    },
+};
+
+/// The main axis of a flex container.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Default)]
+#[allow(dead_code)]
+pub enum Direction {
+    /// Items go from left to right.
+    #[default]
+    Row,
+    /// Items go from top to bottom.
+    Column,
+}
+
+impl Direction {
+    fn main(self, v: Vec2d<i32>) -> i32 {
+        match self {
+            Direction::Row => v.x,
+            Direction::Column => v.y,
+        }
+    }
+
+    fn cross(self, v: Vec2d<i32>) -> i32 {
+        match self {
+            Direction::Row => v.y,
+            Direction::Column => v.x,
+        }
+    }
+}
+
+/// Distribution of the free space along the main axis.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Default)]
+#[allow(dead_code)]
+pub enum Justify {
+    #[default]
+    Start,
+    Center,
+    End,
+    SpaceBetween,
+    SpaceAround,
+    SpaceEvenly,
+}
+
+impl Justify {
+    /// Returns the extra offset of the `idx` item in a line of `n` items with `free` space.
+    fn offset(self, free: i32, idx: i32, n: i32) -> i32 {
+        let spread = free.max(0);
+
+        match self {
+            Justify::Start => 0,
+            Justify::Center => free / 2,
+            Justify::End => free,
+            Justify::SpaceBetween if n > 1 => spread * idx / (n - 1),
+            Justify::SpaceBetween => 0,
+            Justify::SpaceAround => spread * (2 * idx + 1) / (2 * n),
+            Justify::SpaceEvenly => spread * (idx + 1) / (n + 1),
+        }
+    }
+}
+
+/// Alignment of items along the cross axis of their line.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Default)]
+#[allow(dead_code)]
+pub enum CrossAlign {
+    #[default]
+    Start,
+    Center,
+    End,
+    /// Items are stretched to fill the line (within their min and max sizes).
+    Stretch,
+}
+
+/// Per-child flex settings.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct FlexItem {
+    pub grow: f32,
+    pub shrink: f32,
+    /// The initial main size. If `None` the measured size is used.
+    pub basis: Option<i32>,
+    pub min: Vec2d<i32>,
+    pub max: Vec2d<i32>,
+}
+
+#[allow(dead_code)]
+impl FlexItem {
+    pub fn new() -> Self {
+        FlexItem {
+            grow: 0.0,
+            shrink: 1.0,
+            basis: None,
+            min: Vec2d::new(0, 0),
+            max: Vec2d::new(i32::MAX, i32::MAX),
+        }
+    }
+
+    pub fn grow(mut self, grow: f32) -> Self {
+        self.grow = grow;
+        self
+    }
+
+    pub fn shrink(mut self, shrink: f32) -> Self {
+        self.shrink = shrink;
+        self
+    }
+
+    pub fn basis(mut self, basis: i32) -> Self {
+        self.basis = Some(basis);
+        self
+    }
+
+    pub fn min<S>(mut self, min: S) -> Self
+        where
+            S: Into<Vec2d<i32>>,
+    {
+        self.min = min.into();
+        self
+    }
+
+    pub fn max<S>(mut self, max: S) -> Self
+        where
+            S: Into<Vec2d<i32>>,
+    {
+        self.max = max.into();
+        self
+    }
+}
+
+impl Default for FlexItem {
+    fn default() -> Self { FlexItem::new() }
+}
+
+/// The flex layout solver. It works on the integer pixel grid and doesn't need a GL context.
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
+pub struct FlexLayout {
+    pub direction: Direction,
+    pub justify: Justify,
+    pub align: CrossAlign,
+    pub wrap: bool,
+    /// Spacing between items (`x`) and between lines (`y`) for the row direction.
+    /// For the column direction the meaning of components is swapped.
+    pub gap: Vec2d<i32>,
+}
+
+/// A range of items placed on one line.
+#[derive(Copy, Clone, Debug)]
+struct Line {
+    start: usize,
+    end: usize,
+}
+
+impl FlexLayout {
+    /// Computes frames of the items inside of the `frame`.
+    /// Every item is given with its settings and measured content size.
+    pub fn layout(&self, frame: Rect<i32>, items: &[(FlexItem, Vec2d<i32>)]) -> Vec<Rect<i32>> {
+        let dir = self.direction;
+        let space = dir.main(frame.size());
+        let gap = dir.main(self.gap);
+        let cross_gap = dir.cross(self.gap);
+
+        let hypo: Vec<i32> = items
+            .iter()
+            .map(|(item, size)| {
+                let basis = item.basis.unwrap_or_else(|| dir.main(*size));
+                clamp(basis, dir.main(item.min), dir.main(item.max))
+            })
+            .collect();
+
+        let cross: Vec<i32> = items
+            .iter()
+            .map(|(item, size)| clamp(dir.cross(*size), dir.cross(item.min), dir.cross(item.max)))
+            .collect();
+
+        let mut rects = Vec::with_capacity(items.len());
+        let mut cross_offset = 0;
+
+        for line in self.lines(&hypo, space, gap) {
+            let line_cross = if self.wrap {
+                cross[line.start..line.end].iter().cloned().max().unwrap_or(0)
+            } else {
+                dir.cross(frame.size())
+            };
+
+            let line_items = &items[line.start..line.end];
+            let main = resolve_main(line_items, &hypo[line.start..line.end], space, gap, dir);
+
+            let n = main.len() as i32;
+            let free = space - main.iter().sum::<i32>() - gap * (n - 1).max(0);
+            let mut main_offset = 0;
+
+            for (i, ((item, _), &main_size)) in line_items.iter().zip(&main).enumerate() {
+                let idx = line.start + i;
+
+                let cross_size = match self.align {
+                    CrossAlign::Stretch => {
+                        clamp(line_cross, dir.cross(item.min), dir.cross(item.max))
+                    }
+                    _ => cross[idx],
+                };
+
+                let cross_align = match self.align {
+                    CrossAlign::Start | CrossAlign::Stretch => Align::Start,
+                    CrossAlign::Center => Align::Center,
+                    CrossAlign::End => Align::End,
+                };
+
+                let main_pos = main_offset + self.justify.offset(free, i as i32, n);
+                let cross_pos = cross_offset + align_offset(cross_align, line_cross, cross_size);
+
+                rects.push(match dir {
+                    Direction::Row => Rect::new(
+                        (frame.x + main_pos, frame.top() - cross_pos - cross_size),
+                        (main_size, cross_size),
+                    ),
+                    Direction::Column => Rect::new(
+                        (frame.x + cross_pos, frame.top() - main_pos - main_size),
+                        (cross_size, main_size),
+                    ),
+                });
+
+                main_offset += main_size + gap;
+            }
+
+            cross_offset += line_cross + cross_gap;
+        }
+
+        rects
+    }
+
+    /// Splits items into lines by their hypothetical main sizes.
+    fn lines(&self, hypo: &[i32], space: i32, gap: i32) -> Vec<Line> {
+        if !self.wrap {
+            return vec![Line { start: 0, end: hypo.len() }];
+        }
+
+        let mut lines = vec![];
+        let mut start = 0;
+        let mut used = 0;
+
+        for (i, &size) in hypo.iter().enumerate() {
+            if i > start && used + gap + size > space {
+                lines.push(Line { start, end: i });
+                start = i;
+                used = size;
+            } else if i == start {
+                used = size;
+            } else {
+                used += gap + size;
+            }
+        }
+
+        if start < hypo.len() {
+            lines.push(Line { start, end: hypo.len() });
+        }
+
+        lines
+    }
+}
+
+fn clamp(value: i32, min: i32, max: i32) -> i32 { value.min(max).max(min) }
+
+fn align_offset(align: Align, space: i32, size: i32) -> i32 {
+    match align {
+        Align::Start => 0,
+        Align::Center => space / 2 - size / 2,
+        Align::End => space - size,
+    }
+}
+
+/// Grows or shrinks main sizes of a line to fit the `space`.
+fn resolve_main(
+    items: &[(FlexItem, Vec2d<i32>)],
+    hypo: &[i32],
+    space: i32,
+    gap: i32,
+    dir: Direction,
+) -> Vec<i32> {
+    let available = (space - gap * (items.len() as i32 - 1).max(0)) as f32;
+    let growing = available > hypo.iter().sum::<i32>() as f32;
+
+    let mut sizes: Vec<f32> = hypo.iter().map(|&h| h as f32).collect();
+    let mut frozen: Vec<bool> = items
+        .iter()
+        .zip(hypo)
+        .map(|((item, _), &h)| if growing { item.grow <= 0.0 } else { item.shrink <= 0.0 || h == 0 })
+        .collect();
+
+    loop {
+        let used: f32 = (0..items.len())
+            .map(|i| if frozen[i] { sizes[i] } else { hypo[i] as f32 })
+            .sum();
+        let free = available - used;
+
+        let weight = |i: usize| {
+            let item = &items[i].0;
+            if growing { item.grow } else { item.shrink * hypo[i] as f32 }
+        };
+
+        let total: f32 = (0..items.len()).filter(|&i| !frozen[i]).map(weight).sum();
+        if total <= 0.0 || free == 0.0 {
+            break;
+        }
+
+        let mut clamped = false;
+        for i in 0..items.len() {
+            if frozen[i] {
+                continue;
+            }
+
+            let item = &items[i].0;
+            let target = hypo[i] as f32 + free * weight(i) / total;
+            let size = target
+                .min(dir.main(item.max) as f32)
+                .max(dir.main(item.min) as f32);
+
+            sizes[i] = size;
+            if size != target {
+                frozen[i] = true;
+                clamped = true;
+            }
+        }
+
+        if !clamped {
+            break;
+        }
+    }
+
+    // Round the running sum, so that the total size of the line doesn't lose pixels
+    let mut acc = 0.0;
+    let mut prev = 0;
+
+    sizes
+        .into_iter()
+        .map(|size| {
+            acc += size;
+            let cur = acc.round() as i32;
+            let size = cur - prev;
+            prev = cur;
+            size
+        })
+        .collect()
+}
+
+/// Places children with the `FlexLayout`.
+///
+/// By default the container fills the frame it is given.
+pub struct Flex<'a> {
+    layout: FlexLayout,
+    size: Option<Vec2d<i32>>,
+    children: Vec<(FlexItem, Box<dyn Draw + 'a>)>,
+}
+
+#[allow(dead_code)]
+impl<'a> Flex<'a> {
+    pub fn new(direction: Direction) -> Self {
+        Flex {
+            layout: FlexLayout {
+                direction,
+                ..FlexLayout::default()
+            },
+            size: None,
+            children: vec![],
+        }
+    }
+
+    pub fn row() -> Self { Flex::new(Direction::Row) }
+
+    pub fn column() -> Self { Flex::new(Direction::Column) }
+
+    pub fn justify(mut self, justify: Justify) -> Self {
+        self.layout.justify = justify;
+        self
+    }
+
+    pub fn align(mut self, align: CrossAlign) -> Self {
+        self.layout.align = align;
+        self
+    }
+
+    pub fn wrap(mut self) -> Self {
+        self.layout.wrap = true;
+        self
+    }
+
+    pub fn gap<G>(mut self, gap: G) -> Self
+        where
+            G: Into<Vec2d<i32>>,
+    {
+        self.layout.gap = gap.into();
+        self
+    }
+
+    /// Sets the fixed size of the container instead of filling the frame.
+    pub fn size<S>(mut self, size: S) -> Self
+        where
+            S: Into<Vec2d<i32>>,
+    {
+        self.size = Some(size.into());
+        self
+    }
+
+    pub fn with<D>(self, child: D) -> Self
+        where
+            D: Draw + 'a,
+    { self.with_item(FlexItem::new(), child) }
+
+    pub fn with_item<D>(mut self, item: FlexItem, child: D) -> Self
+        where
+            D: Draw + 'a,
+    {
+        self.children.push((item, Box::new(child)));
+        self
+    }
+
+    pub fn push<D>(&mut self, item: FlexItem, child: D)
+        where
+            D: Draw + 'a,
+    { self.children.push((item, Box::new(child))) }
+
+    pub fn layout(&self) -> &FlexLayout { &self.layout }
+
+    pub fn len(&self) -> usize { self.children.len() }
+
+    pub fn is_empty(&self) -> bool { self.children.is_empty() }
+
+    fn items(&self, metrics: &Metrics, params: &DrawParameters) -> Vec<(FlexItem, Vec2d<i32>)> {
+        self.children
+            .iter()
+            .map(|(item, child)| (*item, child.measure(metrics, params)))
+            .collect()
+    }
+
+    fn child_params(&self, metrics: &Metrics, params: DrawParameters) -> Vec<DrawParameters> {
+        let items = self.items(metrics, &params);
+        let size = self.size.unwrap_or_else(|| params.frame.size());
+        let frame = params.render_rect(size);
+
+        let mut child_params = params;
+        child_params.position = Position::LeftTop(0, 0);
+
+        self.layout
+            .layout(frame, &items)
+            .into_iter()
+            .map(|rect| DrawParameters { frame: rect, ..child_params })
+            .collect()
+    }
+}
+
+impl Draw for Flex<'_> {
+    fn draw(&self, render: &mut Render, params: DrawParameters) {
+        let children = self.child_params(render.metrics(), params);
+
+        for ((_, child), params) in self.children.iter().zip(children) {
+            child.draw(render, params);
+        }
+    }
+
+    fn measure(&self, _: &Metrics, params: &DrawParameters) -> Vec2d<i32> {
+        self.size.unwrap_or_else(|| params.frame.size())
+    }
+
+    fn hit(&self, hits: &mut HitTest, params: DrawParameters) {
+        let children = self.child_params(hits.metrics(), params);
+
+        for ((_, child), params) in self.children.iter().zip(children) {
+            child.hit(hits, params);
+        }
+    }
+}
+
+impl std::fmt::Debug for Flex<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Flex")
+            .field("layout", &self.layout)
+            .field("size", &self.size)
+            .field("children", &self.children.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row() -> FlexLayout { FlexLayout::default() }
+
+    fn column() -> FlexLayout {
+        FlexLayout {
+            direction: Direction::Column,
+            ..FlexLayout::default()
+        }
+    }
+
+    fn item(w: i32, h: i32) -> (FlexItem, Vec2d<i32>) { (FlexItem::new(), Vec2d::new(w, h)) }
+
+    fn frame(w: i32, h: i32) -> Rect<i32> { Rect::new((0, 0), (w, h)) }
+
+    #[test]
+    fn start() {
+        let rects = row().layout(frame(100, 10), &[item(10, 4), item(20, 6)]);
+
+        assert_eq!(rects, vec![
+            Rect::new((0, 6), (10, 4)),
+            Rect::new((10, 4), (20, 6)),
+        ]);
+    }
+
+    #[test]
+    fn frame_offset() {
+        let rects = row().layout(Rect::new((5, 7), (100, 10)), &[item(10, 10)]);
+        assert_eq!(rects, vec![Rect::new((5, 7), (10, 10))]);
+    }
+
+    #[test]
+    fn column_goes_down() {
+        let rects = column().layout(frame(10, 100), &[item(4, 10), item(6, 20)]);
+
+        assert_eq!(rects, vec![
+            Rect::new((0, 90), (4, 10)),
+            Rect::new((0, 70), (6, 20)),
+        ]);
+    }
+
+    #[test]
+    fn grow() {
+        let items = [
+            (FlexItem::new().grow(1.0), Vec2d::new(10, 10)),
+            (FlexItem::new().grow(2.0), Vec2d::new(10, 10)),
+            item(10, 10),
+        ];
+        let rects = row().layout(frame(100, 10), &items);
+
+        let widths: Vec<i32> = rects.iter().map(|r| r.width).collect();
+        assert_eq!(widths, vec![33, 57, 10]);
+        assert_eq!(rects[2].right(), 100);
+    }
+
+    #[test]
+    fn grow_rounding_keeps_total() {
+        let items = [
+            (FlexItem::new().grow(1.0), Vec2d::new(0, 1)),
+            (FlexItem::new().grow(1.0), Vec2d::new(0, 1)),
+            (FlexItem::new().grow(1.0), Vec2d::new(0, 1)),
+        ];
+        let rects = row().layout(frame(11, 1), &items);
+
+        let widths: Vec<i32> = rects.iter().map(|r| r.width).collect();
+        assert_eq!(widths.iter().sum::<i32>(), 11);
+        assert!(widths.iter().all(|&w| w == 3 || w == 4));
+        assert_eq!(rects[2].right(), 11);
+    }
+
+    #[test]
+    fn grow_with_max() {
+        let items = [
+            (FlexItem::new().grow(1.0).max((20, i32::MAX)), Vec2d::new(10, 10)),
+            (FlexItem::new().grow(1.0), Vec2d::new(10, 10)),
+        ];
+        let rects = row().layout(frame(100, 10), &items);
+
+        assert_eq!(rects[0].width, 20);
+        assert_eq!(rects[1].width, 80);
+    }
+
+    #[test]
+    fn shrink() {
+        let items = [
+            item(60, 10),
+            (FlexItem::new().shrink(0.0), Vec2d::new(30, 10)),
+            item(30, 10),
+        ];
+        let rects = row().layout(frame(90, 10), &items);
+
+        // 30 px of overflow is taken from the shrinkable items proportional to their sizes
+        let widths: Vec<i32> = rects.iter().map(|r| r.width).collect();
+        assert_eq!(widths, vec![40, 30, 20]);
+    }
+
+    #[test]
+    fn shrink_with_min() {
+        let items = [
+            (FlexItem::new().min((25, 0)), Vec2d::new(30, 10)),
+            item(30, 10),
+        ];
+        let rects = row().layout(frame(40, 10), &items);
+
+        assert_eq!(rects[0].width, 25);
+        assert_eq!(rects[1].width, 15);
+    }
+
+    #[test]
+    fn basis() {
+        let items = [(FlexItem::new().basis(40), Vec2d::new(10, 10))];
+        let rects = row().layout(frame(100, 10), &items);
+
+        assert_eq!(rects[0].width, 40);
+    }
+
+    #[test]
+    fn justify() {
+        let items = [item(10, 10), item(10, 10), item(10, 10)];
+        let xs = |justify| {
+            let layout = FlexLayout { justify, ..row() };
+            layout
+                .layout(frame(100, 10), &items)
+                .iter()
+                .map(|r| r.x)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(xs(Justify::Start), vec![0, 10, 20]);
+        assert_eq!(xs(Justify::Center), vec![35, 45, 55]);
+        assert_eq!(xs(Justify::End), vec![70, 80, 90]);
+        assert_eq!(xs(Justify::SpaceBetween), vec![0, 45, 90]);
+        assert_eq!(xs(Justify::SpaceAround), vec![11, 45, 78]);
+        assert_eq!(xs(Justify::SpaceEvenly), vec![17, 45, 72]);
+    }
+
+    #[test]
+    fn cross_align() {
+        let items = [item(10, 4), (FlexItem::new().max((i32::MAX, 8)), Vec2d::new(10, 2))];
+        let rects = |align| FlexLayout { align, ..row() }.layout(frame(100, 11), &items);
+
+        let start = rects(CrossAlign::Start);
+        assert_eq!(start[0], Rect::new((0, 7), (10, 4)));
+
+        let center = rects(CrossAlign::Center);
+        assert_eq!(center[0], Rect::new((0, 4), (10, 4)));
+
+        let end = rects(CrossAlign::End);
+        assert_eq!(end[0].y, 0);
+
+        let stretch = rects(CrossAlign::Stretch);
+        assert_eq!(stretch[0], Rect::new((0, 0), (10, 11)));
+        assert_eq!(stretch[1], Rect::new((10, 3), (10, 8)));
+    }
+
+    #[test]
+    fn wrap() {
+        let layout = FlexLayout {
+            wrap: true,
+            gap: Vec2d::new(2, 1),
+            ..row()
+        };
+        let items = [item(10, 4), item(10, 6), item(10, 4), item(25, 3)];
+        let rects = layout.layout(frame(34, 20), &items);
+
+        assert_eq!(rects, vec![
+            Rect::new((0, 16), (10, 4)),
+            Rect::new((12, 14), (10, 6)),
+            Rect::new((24, 16), (10, 4)),
+            Rect::new((0, 10), (25, 3)),
+        ]);
+    }
+
+    #[test]
+    fn wrap_oversized_item_takes_own_line() {
+        let layout = FlexLayout { wrap: true, ..row() };
+        let items = [item(50, 1), item(5, 1)];
+        let rects = layout.layout(frame(20, 10), &items);
+
+        assert_eq!(rects[0].y, 9);
+        assert_eq!(rects[1].y, 8);
+    }
+}
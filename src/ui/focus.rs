@@ -0,0 +1,90 @@
+use std::cell::Cell;
+
+use crate::{
+    common::Rect,
+    graphics::Render,
+    input::{
+        Event,
+        FocusSlot,
+        Input,
+        Key,
+        MouseButton,
+        TabStop,
+    },
+};
+
+/// The keyboard focus of a widget, kept in sync with the `Focus` of the `Render`.
+#[derive(Debug, Default)]
+pub(super) struct Focusable {
+    stop: TabStop,
+    focused: Cell<bool>,
+    /// The change by the user that is applied at the next drawing.
+    request: Cell<Option<bool>>,
+}
+
+impl Focusable {
+    pub(super) fn stop(&self) -> TabStop { self.stop }
+
+    pub(super) fn set_stop(&mut self, stop: TabStop) { self.stop = stop }
+
+    pub(super) fn is_focused(&self) -> bool { self.focused.get() }
+
+    /// Changes the focus at the next drawing.
+    pub(super) fn request(&self, focused: bool) {
+        self.focused.set(focused);
+        self.request.set(Some(focused));
+    }
+
+    /// Changes the focus while drawing, between `begin` and `end`.
+    pub(super) fn set(&self, focused: bool) { self.focused.set(focused) }
+
+    /// Adds the widget to the focus of the frame and takes the focus from it.
+    /// The disabled widget is skipped by keys and loses the focus.
+    pub(super) fn begin(&self, render: &mut Render, enabled: bool) -> FocusSlot {
+        let stop = if enabled { self.stop } else { TabStop { index: -1, ..self.stop } };
+        let slot = render.focus_mut().add(stop);
+
+        let focused = match self.request.take() {
+            Some(focused) => focused,
+            None => render.focus().is_focused(slot),
+        };
+
+        self.focused.set(focused && enabled);
+        slot
+    }
+
+    /// Gives the focus back and draws the ring around the `rect` if the widget is focused.
+    pub(super) fn end(&self, render: &mut Render, slot: FocusSlot, rect: Rect<i32>) {
+        render.focus_mut().set(slot, self.is_focused());
+
+        if let (true, Some(color)) = (self.is_focused(), render.focus().ring()) {
+            let ring = rect.outset(1);
+            let (w, h) = (ring.width, ring.height);
+
+            render.fill_rect(Rect::new(ring.pos(), (w, 1)), color);
+            render.fill_rect(Rect::new((ring.x, ring.top() - 1), (w, 1)), color);
+            render.fill_rect(Rect::new(ring.pos(), (1, h)), color);
+            render.fill_rect(Rect::new((ring.right() - 1, ring.y), (1, h)), color);
+        }
+    }
+
+    /// Focuses the widget by pressing the left button over the `rect`, or drops the focus
+    /// by pressing it outside.
+    pub(super) fn click(&self, input: &Input, rect: Rect<i32>) {
+        for event in input.events() {
+            if let Event::MouseDown(MouseButton::Left, pos) = event {
+                self.focused.set(rect.intersects_point(pos));
+            }
+        }
+    }
+
+    /// Whether the focused widget is activated by Enter or Space.
+    pub(super) fn activated(&self, input: &Input) -> bool {
+        let activates = |event: Event| match event {
+            Event::KeyDown(key) => matches!(key, Key::Return | Key::NumpadEnter | Key::Space),
+            _ => false,
+        };
+
+        self.is_focused() && input.events().any(activates)
+    }
+}
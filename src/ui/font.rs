@@ -7,6 +7,8 @@ use crate::{
         Render,
         Draw,
         DrawParameters,
+        Metrics,
+        HitTest,
         FontStyle,
     },
 };
@@ -53,4 +55,15 @@ impl<U> Draw for Font<U>
         params.font_style = self.style;
         self.ui.draw(render, params);
     }
+
+    fn measure(&self, metrics: &Metrics, params: &DrawParameters) -> Vec2d<i32> {
+        let mut params = *params;
+        params.font_style = self.style;
+        self.ui.measure(metrics, &params)
+    }
+
+    fn hit(&self, hits: &mut HitTest, mut params: DrawParameters) {
+        params.font_style = self.style;
+        self.ui.hit(hits, params);
+    }
 }
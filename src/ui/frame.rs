@@ -0,0 +1,143 @@
+use crate::{
+    common::{
+        Position,
+        Rect,
+        Vec2d,
+    },
+    graphics::{
+        Render,
+        Draw,
+        DrawParameters,
+        Metrics,
+        HitTest,
+    },
+};
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Area {
+    /// The rect relative to the bottom left corner of the parent frame.
+    Rect(Rect<i32>),
+    /// The size of the frame. It's positioned in the parent frame with the inherited `Position`.
+    Size(Vec2d<i32>),
+}
+
+/// Narrows the frame of its content, so the content is positioned relative to this frame.
+#[derive(Debug)]
+pub struct Frame<U> {
+    area: Area,
+    ui: U,
+}
+
+#[allow(dead_code)]
+impl<U> Frame<U>
+    where
+        U: Draw,
+{
+    pub fn new(area: Area, ui: U) -> Self { Frame { area, ui } }
+
+    pub fn rect(rect: Rect<i32>, ui: U) -> Self { Frame::new(Area::Rect(rect), ui) }
+
+    pub fn sized<S>(size: S, ui: U) -> Self
+        where
+            S: Into<Vec2d<i32>>,
+    { Frame::new(Area::Size(size.into()), ui) }
+
+    pub fn area(&self) -> Area { self.area }
+
+    pub fn frame(&self, params: &DrawParameters) -> Rect<i32> {
+        match self.area {
+            Area::Rect(rect) => rect.translated(params.frame.pos()),
+            Area::Size(size) => params.render_rect(size),
+        }
+    }
+}
+
+impl<U> Draw for Frame<U>
+    where
+        U: Draw,
+{
+    fn draw(&self, render: &mut Render, mut params: DrawParameters) {
+        params.frame = self.frame(&params);
+        params.position = Position::default();
+        self.ui.draw(render, params);
+    }
+
+    fn measure(&self, _: &Metrics, _: &DrawParameters) -> Vec2d<i32> {
+        match self.area {
+            Area::Rect(rect) => Vec2d::new(rect.right(), rect.top()),
+            Area::Size(size) => size,
+        }
+    }
+
+    fn hit(&self, hits: &mut HitTest, mut params: DrawParameters) {
+        params.frame = self.frame(&params);
+        params.position = Position::default();
+        self.ui.hit(hits, params);
+    }
+}
+
+impl<U> std::ops::Deref for Frame<U> {
+    type Target = U;
+
+    fn deref(&self) -> &Self::Target { &self.ui }
+}
+
+impl<U> std::ops::DerefMut for Frame<U> {
+    fn deref_mut(&mut self) -> &mut Self::Target { &mut self.ui }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::Pos;
+
+    fn rects<U>(ui: U, frame: Rect<i32>) -> Vec<Rect<i32>>
+        where
+            U: Draw,
+    {
+        HitTest::run(&Metrics::test(), &ui, frame)
+            .iter()
+            .map(|hit| hit.rect)
+            .collect()
+    }
+
+    #[test]
+    fn rect_area() {
+        let frame = Rect::new((0, 0), (100, 50));
+
+        let ui = Frame::rect(Rect::new((10, 5), (30, 20)), Pos::right_top(2, 3, "a"));
+        assert_eq!(rects(ui, frame), vec![Rect::new((33, 16), (5, 6))]);
+
+        // The area is relative to the parent frame
+        let ui = Frame::rect(Rect::new((10, 5), (30, 20)), Pos::left_bot(1, 1, "a"));
+        assert_eq!(rects(ui, Rect::new((7, 3), (100, 50))), vec![Rect::new((18, 9), (5, 6))]);
+
+        let params = DrawParameters::new(frame);
+        let ui = Frame::rect(Rect::new((10, 5), (30, 20)), "a");
+        assert_eq!(ui.measure(&Metrics::test(), &params), Vec2d::new(40, 25));
+    }
+
+    #[test]
+    fn size_area() {
+        let frame = Rect::new((0, 0), (100, 50));
+
+        let ui = Pos::right_bot(0, 0, Frame::sized((20, 10), Pos::left_top(0, 0, "a")));
+        assert_eq!(rects(ui, frame), vec![Rect::new((80, 4), (5, 6))]);
+
+        let params = DrawParameters::new(frame);
+        let ui = Frame::sized((20, 10), "a");
+        assert_eq!(ui.measure(&Metrics::test(), &params), Vec2d::new(20, 10));
+    }
+
+    #[test]
+    fn nested() {
+        let inner = Frame::rect(Rect::new((4, 2), (10, 10)), Pos::left_bot(1, 1, "a"));
+        let ui = Frame::rect(Rect::new((10, 5), (30, 20)), inner);
+        assert_eq!(rects(ui, Rect::new((0, 0), (100, 50))), vec![Rect::new((15, 8), (5, 6))]);
+
+        // The inner frame is centered in the outer one by default
+        let inner = Frame::sized((10, 10), Pos::right_top(0, 0, "a"));
+        let ui = Frame::rect(Rect::new((10, 5), (30, 20)), inner);
+        assert_eq!(rects(ui, Rect::new((0, 0), (100, 50))), vec![Rect::new((25, 14), (5, 6))]);
+    }
+}
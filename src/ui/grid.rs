@@ -0,0 +1,536 @@
+use crate::{
+    common::{
+        Position,
+        Rect,
+        Vec2d,
+    },
+    graphics::{
+        Render,
+        Draw,
+        DrawParameters,
+        Metrics,
+        HitTest,
+    },
+};
+
+/// The size of a grid row or column.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[allow(dead_code)]
+pub enum Track {
+    /// Fixed size in pixels.
+    Fixed(i32),
+    /// A share of the space left after fixed and auto tracks.
+    Fraction(i32),
+    /// The largest size of the children placed in the track. Spanned children spread
+    /// the missing size over their auto tracks, unless they cover a fraction track.
+    Auto,
+}
+
+/// Placement of a child in the grid. Rows go from top to bottom, columns go from left to right.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct GridCell {
+    pub row: usize,
+    pub col: usize,
+    pub row_span: usize,
+    pub col_span: usize,
+    /// Position of the child inside of the cell.
+    pub position: Position,
+}
+
+#[allow(dead_code)]
+impl GridCell {
+    pub fn new(row: usize, col: usize) -> Self {
+        GridCell {
+            row,
+            col,
+            row_span: 1,
+            col_span: 1,
+            position: Position::default(),
+        }
+    }
+
+    pub fn span(mut self, rows: usize, cols: usize) -> Self {
+        self.row_span = rows.max(1);
+        self.col_span = cols.max(1);
+        self
+    }
+
+    pub fn position(mut self, position: Position) -> Self {
+        self.position = position;
+        self
+    }
+}
+
+/// The grid layout solver. It doesn't need a GL context.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct GridLayout {
+    pub rows: Vec<Track>,
+    pub columns: Vec<Track>,
+    pub gap: Vec2d<i32>,
+}
+
+impl GridLayout {
+    /// Computes frames of the cells inside of the `frame`.
+    /// Every cell is given with the measured size of its content.
+    /// Cells placed outside of the grid get `None`.
+    pub fn layout(&self, frame: Rect<i32>, cells: &[(GridCell, Vec2d<i32>)]) -> Vec<Option<Rect<i32>>> {
+        let (widths, heights) = self.track_sizes(frame.size(), cells);
+
+        let xs = offsets(&widths, self.gap.x);
+        let ys = offsets(&heights, self.gap.y);
+
+        cells
+            .iter()
+            .map(|(cell, _)| {
+                if cell.row >= heights.len() || cell.col >= widths.len() {
+                    return None;
+                }
+
+                let last_col = (cell.col + cell.col_span.max(1)).min(widths.len()) - 1;
+                let last_row = (cell.row + cell.row_span.max(1)).min(heights.len()) - 1;
+
+                let left = xs[cell.col];
+                let right = xs[last_col] + widths[last_col];
+                let top = ys[cell.row];
+                let bot = ys[last_row] + heights[last_row];
+
+                Some(Rect::new(
+                    (frame.x + left, frame.top() - bot),
+                    (right - left, bot - top),
+                ))
+            })
+            .collect()
+    }
+
+    /// Returns the size of the grid, where fraction tracks are collapsed to zero.
+    pub fn content_size(&self, cells: &[(GridCell, Vec2d<i32>)]) -> Vec2d<i32> {
+        let (widths, heights) = self.track_sizes(Vec2d::default(), cells);
+        Vec2d::new(total(&widths, self.gap.x), total(&heights, self.gap.y))
+    }
+
+    /// Whether the grid stretches along `x` and `y` axes.
+    pub fn has_fractions(&self) -> (bool, bool) {
+        let is_fraction = |track: &Track| matches!(track, Track::Fraction(_));
+        (self.columns.iter().any(is_fraction), self.rows.iter().any(is_fraction))
+    }
+
+    fn track_sizes(&self, space: Vec2d<i32>, cells: &[(GridCell, Vec2d<i32>)]) -> (Vec<i32>, Vec<i32>) {
+        let columns: Vec<Span> = cells
+            .iter()
+            .map(|(cell, size)| Span { start: cell.col, len: cell.col_span.max(1), size: size.x })
+            .collect();
+
+        let rows: Vec<Span> = cells
+            .iter()
+            .map(|(cell, size)| Span { start: cell.row, len: cell.row_span.max(1), size: size.y })
+            .collect();
+
+        let auto_widths = auto_sizes(&self.columns, &columns, self.gap.x);
+        let auto_heights = auto_sizes(&self.rows, &rows, self.gap.y);
+
+        (
+            resolve_tracks(&self.columns, &auto_widths, space.x, self.gap.x),
+            resolve_tracks(&self.rows, &auto_heights, space.y, self.gap.y),
+        )
+    }
+}
+
+/// Tracks occupied by a cell along one axis, and the size of its content.
+#[derive(Copy, Clone, Debug)]
+struct Span {
+    start: usize,
+    len: usize,
+    size: i32,
+}
+
+/// Returns sizes of auto tracks. Cells of one track are taken first,
+/// then spanned cells from the shortest spread the size they miss over their auto tracks.
+fn auto_sizes(tracks: &[Track], spans: &[Span], gap: i32) -> Vec<i32> {
+    let mut autos = vec![0; tracks.len()];
+
+    for span in spans.iter().filter(|span| span.len == 1) {
+        if let Some(auto) = autos.get_mut(span.start) {
+            *auto = span.size.max(*auto);
+        }
+    }
+
+    let mut spanned: Vec<&Span> = spans
+        .iter()
+        .filter(|span| span.len > 1 && span.start < tracks.len())
+        .collect();
+    spanned.sort_by_key(|span| span.len);
+
+    for span in spanned {
+        let covered = span.start..(span.start + span.len).min(tracks.len());
+
+        // Fraction tracks take the free space, so the cell is stretched anyway
+        if tracks[covered.clone()].iter().any(|track| matches!(track, Track::Fraction(_))) {
+            continue;
+        }
+
+        let used: i32 = covered
+            .clone()
+            .map(|i| match tracks[i] {
+                Track::Fixed(size) => size,
+                _ => autos[i],
+            })
+            .sum();
+
+        let missing = span.size - used - gap * (covered.len() as i32 - 1);
+        let auto: Vec<usize> = covered.filter(|&i| tracks[i] == Track::Auto).collect();
+        if missing <= 0 || auto.is_empty() {
+            continue;
+        }
+
+        // The remainder goes to the first tracks
+        let n = auto.len() as i32;
+        for (k, &i) in auto.iter().enumerate() {
+            autos[i] += missing / n + ((k as i32) < missing % n) as i32;
+        }
+    }
+
+    autos
+}
+
+fn resolve_tracks(tracks: &[Track], autos: &[i32], space: i32, gap: i32) -> Vec<i32> {
+    let mut sizes: Vec<i32> = tracks
+        .iter()
+        .zip(autos)
+        .map(|(track, &auto)| match track {
+            Track::Fixed(size) => *size,
+            Track::Fraction(_) => 0,
+            Track::Auto => auto,
+        })
+        .collect();
+
+    let fractions: i32 = tracks
+        .iter()
+        .map(|track| match track {
+            Track::Fraction(fr) => *fr,
+            _ => 0,
+        })
+        .sum();
+
+    if fractions > 0 {
+        let free = (space - total(&sizes, gap)).max(0);
+
+        // Round the running sum, so that the fraction tracks fill the free space exactly
+        let mut acc = 0;
+        let mut prev = 0;
+
+        for (size, track) in sizes.iter_mut().zip(tracks) {
+            if let Track::Fraction(fr) = track {
+                acc += fr;
+                let cur = free * acc / fractions;
+                *size = cur - prev;
+                prev = cur;
+            }
+        }
+    }
+
+    sizes
+}
+
+fn total(sizes: &[i32], gap: i32) -> i32 {
+    sizes.iter().sum::<i32>() + gap * (sizes.len() as i32 - 1).max(0)
+}
+
+fn offsets(sizes: &[i32], gap: i32) -> Vec<i32> {
+    sizes
+        .iter()
+        .scan(0, |offset, size| {
+            let cur = *offset;
+            *offset += size + gap;
+            Some(cur)
+        })
+        .collect()
+}
+
+/// Places children in cells of the `GridLayout`.
+///
+/// The grid fills the frame along an axis that has fraction tracks,
+/// otherwise it takes the size of its tracks.
+pub struct Grid<'a> {
+    layout: GridLayout,
+    children: Vec<(GridCell, Box<dyn Draw + 'a>)>,
+}
+
+#[allow(dead_code)]
+impl<'a> Grid<'a> {
+    pub fn new() -> Self {
+        Grid {
+            layout: GridLayout::default(),
+            children: vec![],
+        }
+    }
+
+    pub fn rows<T>(mut self, rows: T) -> Self
+        where
+            T: Into<Vec<Track>>,
+    {
+        self.layout.rows = rows.into();
+        self
+    }
+
+    pub fn columns<T>(mut self, columns: T) -> Self
+        where
+            T: Into<Vec<Track>>,
+    {
+        self.layout.columns = columns.into();
+        self
+    }
+
+    pub fn gap<G>(mut self, gap: G) -> Self
+        where
+            G: Into<Vec2d<i32>>,
+    {
+        self.layout.gap = gap.into();
+        self
+    }
+
+    pub fn with<D>(mut self, cell: GridCell, child: D) -> Self
+        where
+            D: Draw + 'a,
+    {
+        self.children.push((cell, Box::new(child)));
+        self
+    }
+
+    pub fn cell<D>(self, row: usize, col: usize, child: D) -> Self
+        where
+            D: Draw + 'a,
+    { self.with(GridCell::new(row, col), child) }
+
+    pub fn push<D>(&mut self, cell: GridCell, child: D)
+        where
+            D: Draw + 'a,
+    { self.children.push((cell, Box::new(child))) }
+
+    pub fn layout(&self) -> &GridLayout { &self.layout }
+
+    pub fn len(&self) -> usize { self.children.len() }
+
+    pub fn is_empty(&self) -> bool { self.children.is_empty() }
+
+    fn cells(&self, metrics: &Metrics, params: &DrawParameters) -> Vec<(GridCell, Vec2d<i32>)> {
+        self.children
+            .iter()
+            .map(|(cell, child)| (*cell, child.measure(metrics, params)))
+            .collect()
+    }
+
+    fn size(&self, cells: &[(GridCell, Vec2d<i32>)], params: &DrawParameters) -> Vec2d<i32> {
+        let content = self.layout.content_size(cells);
+        let (fill_x, fill_y) = self.layout.has_fractions();
+
+        Vec2d::new(
+            if fill_x { params.frame.width } else { content.x },
+            if fill_y { params.frame.height } else { content.y },
+        )
+    }
+
+    /// Returns parameters of children. Children out of the grid are skipped.
+    fn child_params(&self, metrics: &Metrics, params: DrawParameters)
+                    -> Vec<Option<DrawParameters>> {
+        let cells = self.cells(metrics, &params);
+        let frame = params.render_rect(self.size(&cells, &params));
+
+        self.layout
+            .layout(frame, &cells)
+            .into_iter()
+            .zip(&self.children)
+            .map(|(rect, (cell, _))| rect.map(|rect| DrawParameters {
+                frame: rect,
+                position: cell.position,
+                ..params
+            }))
+            .collect()
+    }
+}
+
+impl Default for Grid<'_> {
+    fn default() -> Self { Grid::new() }
+}
+
+impl Draw for Grid<'_> {
+    fn draw(&self, render: &mut Render, params: DrawParameters) {
+        let children = self.child_params(render.metrics(), params);
+
+        for ((_, child), params) in self.children.iter().zip(children) {
+            if let Some(params) = params {
+                child.draw(render, params);
+            }
+        }
+    }
+
+    fn measure(&self, metrics: &Metrics, params: &DrawParameters) -> Vec2d<i32> {
+        self.size(&self.cells(metrics, params), params)
+    }
+
+    fn hit(&self, hits: &mut HitTest, params: DrawParameters) {
+        let children = self.child_params(hits.metrics(), params);
+
+        for ((_, child), params) in self.children.iter().zip(children) {
+            if let Some(params) = params {
+                child.hit(hits, params);
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for Grid<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Grid")
+            .field("layout", &self.layout)
+            .field("children", &self.children.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cell(row: usize, col: usize, w: i32, h: i32) -> (GridCell, Vec2d<i32>) {
+        (GridCell::new(row, col), Vec2d::new(w, h))
+    }
+
+    #[test]
+    fn fixed() {
+        let layout = GridLayout {
+            rows: vec![Track::Fixed(10), Track::Fixed(20)],
+            columns: vec![Track::Fixed(5), Track::Fixed(15)],
+            gap: Vec2d::new(1, 2),
+        };
+        let rects = layout.layout(Rect::new((0, 0), (100, 100)), &[
+            cell(0, 0, 1, 1),
+            cell(1, 1, 1, 1),
+        ]);
+
+        assert_eq!(rects, vec![
+            Some(Rect::new((0, 90), (5, 10))),
+            Some(Rect::new((6, 68), (15, 20))),
+        ]);
+    }
+
+    #[test]
+    fn fractions() {
+        let layout = GridLayout {
+            rows: vec![Track::Fixed(10)],
+            columns: vec![Track::Fixed(10), Track::Fraction(1), Track::Fraction(2)],
+            gap: Vec2d::new(2, 0),
+        };
+        let rects = layout.layout(Rect::new((3, 0), (50, 10)), &[
+            cell(0, 1, 0, 0),
+            cell(0, 2, 0, 0),
+        ]);
+
+        // 50 - 10 - 2 * 2 = 36 px are split as 1:2
+        assert_eq!(rects, vec![
+            Some(Rect::new((15, 0), (12, 10))),
+            Some(Rect::new((29, 0), (24, 10))),
+        ]);
+        assert_eq!(rects[1].unwrap().right(), 53);
+    }
+
+    #[test]
+    fn fraction_rounding() {
+        let sizes = resolve_tracks(&[Track::Fraction(1); 3], &[0; 3], 10, 0);
+
+        assert_eq!(sizes.iter().sum::<i32>(), 10);
+        assert!(sizes.iter().all(|&s| s == 3 || s == 4));
+    }
+
+    #[test]
+    fn auto() {
+        let layout = GridLayout {
+            rows: vec![Track::Auto, Track::Auto],
+            columns: vec![Track::Auto, Track::Auto],
+            gap: Vec2d::new(0, 0),
+        };
+        let cells = [
+            cell(0, 0, 4, 3),
+            cell(1, 0, 6, 2),
+            cell(0, 1, 2, 5),
+        ];
+
+        assert_eq!(layout.content_size(&cells), Vec2d::new(8, 7));
+
+        let rects = layout.layout(Rect::new((0, 0), (8, 7)), &cells);
+        assert_eq!(rects[1], Some(Rect::new((0, 0), (6, 2))));
+    }
+
+    #[test]
+    fn spanned_auto() {
+        let layout = GridLayout {
+            rows: vec![Track::Auto],
+            columns: vec![Track::Auto, Track::Fixed(3), Track::Auto],
+            gap: Vec2d::new(1, 0),
+        };
+        let cells = [
+            cell(0, 0, 4, 1),
+            (GridCell::new(0, 0).span(1, 3), Vec2d::new(20, 1)),
+        ];
+
+        // 20 - 4 - 3 - 2 * 1 = 11 px are spread over both auto columns
+        let rects = layout.layout(Rect::new((0, 0), (20, 1)), &cells);
+        assert_eq!(rects[0], Some(Rect::new((0, 0), (10, 1))));
+        assert_eq!(rects[1], Some(Rect::new((0, 0), (20, 1))));
+        assert_eq!(layout.content_size(&cells), Vec2d::new(20, 1));
+
+        // The fraction column takes the free space instead
+        let layout = GridLayout {
+            columns: vec![Track::Auto, Track::Fraction(1)],
+            ..layout
+        };
+        assert_eq!(layout.content_size(&cells), Vec2d::new(5, 1));
+    }
+
+    #[test]
+    fn zero_span() {
+        let layout = GridLayout {
+            rows: vec![Track::Fixed(4), Track::Fixed(4)],
+            columns: vec![Track::Fixed(4), Track::Auto],
+            gap: Vec2d::new(1, 1),
+        };
+        let zero = |row, col| GridCell { row_span: 0, col_span: 0, ..GridCell::new(row, col) };
+        let cells = [
+            (zero(0, 0), Vec2d::new(2, 2)),
+            (zero(1, 1), Vec2d::new(3, 2)),
+        ];
+        let rects = layout.layout(Rect::new((0, 0), (8, 9)), &cells);
+
+        // Zero spans are taken as 1
+        assert_eq!(rects, vec![
+            Some(Rect::new((0, 5), (4, 4))),
+            Some(Rect::new((5, 0), (3, 4))),
+        ]);
+    }
+
+    #[test]
+    fn spans_are_clamped() {
+        let layout = GridLayout {
+            rows: vec![Track::Fixed(4), Track::Fixed(4)],
+            columns: vec![Track::Fixed(4), Track::Fixed(4)],
+            gap: Vec2d::new(1, 1),
+        };
+        let cells = [
+            (GridCell::new(0, 0).span(5, 5), Vec2d::default()),
+            cell(2, 0, 0, 0),
+        ];
+        let rects = layout.layout(Rect::new((0, 0), (9, 9)), &cells);
+
+        assert_eq!(rects, vec![Some(Rect::new((0, 0), (9, 9))), None]);
+    }
+
+    #[test]
+    fn has_fractions() {
+        let layout = GridLayout {
+            rows: vec![Track::Auto],
+            columns: vec![Track::Fraction(1)],
+            gap: Vec2d::default(),
+        };
+
+        assert_eq!(layout.has_fractions(), (true, false));
+    }
+}
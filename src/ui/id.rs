@@ -0,0 +1,57 @@
+use crate::{
+    common::Vec2d,
+    graphics::{
+        Render,
+        Draw,
+        DrawParameters,
+        Metrics,
+        HitTest,
+    },
+};
+
+/// Marks the content for the hit test. Everything inside is reported with the `id`.
+#[derive(Debug)]
+pub struct Id<U> {
+    id: u32,
+    ui: U,
+}
+
+#[allow(dead_code)]
+impl<U> Id<U>
+    where
+        U: Draw,
+{
+    pub fn new(id: u32, ui: U) -> Self { Id { id, ui } }
+
+    pub fn id(&self) -> u32 { self.id }
+}
+
+impl<U> Draw for Id<U>
+    where
+        U: Draw,
+{
+    fn draw(&self, render: &mut Render, params: DrawParameters) { self.ui.draw(render, params) }
+
+    fn measure(&self, metrics: &Metrics, params: &DrawParameters) -> Vec2d<i32> {
+        self.ui.measure(metrics, params)
+    }
+
+    fn hit(&self, hits: &mut HitTest, params: DrawParameters) {
+        let size = self.ui.measure(hits.metrics(), &params);
+
+        hits.push_id(self.id);
+        hits.record(params.render_rect(size));
+        self.ui.hit(hits, params);
+        hits.pop_id();
+    }
+}
+
+impl<U> std::ops::Deref for Id<U> {
+    type Target = U;
+
+    fn deref(&self) -> &Self::Target { &self.ui }
+}
+
+impl<U> std::ops::DerefMut for Id<U> {
+    fn deref_mut(&mut self) -> &mut Self::Target { &mut self.ui }
+}
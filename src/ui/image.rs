@@ -1,34 +1,116 @@
-use crate::graphics::{
-    Texture,
-    Render,
-    Draw,
-    DrawParameters,
+use std::rc::Rc;
+
+use crate::{
+    common::{
+        Color,
+        Rect,
+        Vec2d,
+    },
+    graphics::{
+        AssetError,
+        AtlasId,
+        Texture,
+        TextureAtlas,
+        Render,
+        Draw,
+        DrawParameters,
+        Metrics,
+    },
 };
 
-#[derive(Debug)]
+/// The image from the file or from the region of the `TextureAtlas`.
+#[derive(Clone, Debug)]
 pub struct Image {
     file: String,
-    texture: Texture,
+    texture: Rc<Texture>,
+    /// The part of the texture with the image.
+    st: Rect<f32>,
+    size: Vec2d<i32>,
 }
 
 impl Image {
-    pub fn new<S>(file: S, _render: &Render) -> Self
+    /// Loads the image by assets of the `Render`, so the same file is loaded once.
+    pub fn new<S>(file: S, render: &Render) -> Result<Self, AssetError>
         where
             S: Into<String>,
     {
         let file = file.into();
-        let texture = Texture::from_file(file.as_str()).unwrap();
+        let texture = render.assets().texture(file.as_str(), render.is_srgb())?;
+
+        Ok(Image {
+            file,
+            st: Rect::new((0.0, 0.0), (1.0, 1.0)),
+            size: texture.size(),
+            texture,
+        })
+    }
+
+    /// Creates the image of the atlas region. It shares the texture with the atlas.
+    pub fn from_atlas(atlas: &TextureAtlas, id: AtlasId) -> Self {
+        let region = atlas.region(id);
+
+        Image {
+            file: region.name.clone(),
+            texture: Rc::clone(atlas.texture(id)),
+            st: region.st,
+            size: region.rect.size(),
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn file(&self) -> &str { self.file.as_str() }
+
+    /// Draws the part of the image stretched over the rect.
+    /// The `st` is in fractions of the image size with the origin at the bottom left corner.
+    pub fn draw_part(&self, render: &mut Render, color: Color, rect: Rect<i32>, st: Rect<f32>) {
+        render.set_color(color);
+        render.set_texture(&self.texture);
+        render.draw_rect_st(rect.cast(), part_st(self.st, st));
+    }
 
-        Image { file, texture }
+    /// Draws the image stretched over the rect.
+    pub fn draw_stretched(&self, render: &mut Render, color: Color, rect: Rect<i32>) {
+        self.draw_part(render, color, rect, Rect::new((0.0, 0.0), (1.0, 1.0)))
     }
 }
 
 impl Draw for Image {
     fn draw(&self, render: &mut Render, params: DrawParameters) {
-        let rect = params.render_rect(self.texture.size());
+        let rect = params.render_rect(self.size);
+        self.draw_stretched(render, params.color, rect);
+    }
 
-        render.set_color(params.color);
-        render.set_texture(&self.texture);
-        render.draw_rect(rect.cast());
+    fn measure(&self, _: &Metrics, _: &DrawParameters) -> Vec2d<i32> { self.size }
+}
+
+/// Converts the part of the image with the origin at the bottom left corner to coordinates
+/// of the texture, where the image is at `image_st` and rows go from the top one.
+fn part_st(image_st: Rect<f32>, part: Rect<f32>) -> Rect<f32> {
+    let top = 1.0 - part.y - part.height;
+
+    Rect::new(
+        (image_st.x + part.x * image_st.width, image_st.y + top * image_st.height),
+        (part.width * image_st.width, part.height * image_st.height),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part() {
+        let whole = Rect::new((0.0, 0.0), (1.0, 1.0));
+        let region = Rect::new((0.5, 0.25), (0.5, 0.5));
+
+        // The bottom quarter is the last rows of the texture
+        let bottom = Rect::new((0.0, 0.0), (1.0, 0.25));
+        assert_eq!(part_st(whole, bottom), Rect::new((0.0, 0.75), (1.0, 0.25)));
+        assert_eq!(part_st(region, bottom), Rect::new((0.5, 0.625), (0.5, 0.125)));
+
+        // The left half is the same in both
+        let left = Rect::new((0.0, 0.0), (0.5, 1.0));
+        assert_eq!(part_st(whole, left), left);
+        assert_eq!(part_st(region, whole), region);
     }
 }
@@ -0,0 +1,129 @@
+use crate::{
+    common::{
+        Insets,
+        Vec2d,
+    },
+    graphics::{
+        Render,
+        Draw,
+        DrawParameters,
+        Metrics,
+        HitTest,
+    },
+};
+
+/// Shrinks the frame given to the content, and takes the space of insets in layouts.
+///
+/// The `Padding` places the padded box of the content's size by the inherited `Position`,
+/// so the content is positioned inside that box. The margin shrinks the whole frame instead,
+/// so `Margin::uniform(4, Pos::right_bot(0, 0, ui))` places the `ui` 4 pixels from the corner,
+/// while the same `Padding` centers it.
+#[derive(Debug)]
+pub struct Margin<U> {
+    insets: Insets<i32>,
+    ui: U,
+}
+
+#[allow(dead_code)]
+impl<U> Margin<U>
+    where
+        U: Draw,
+{
+    pub fn new<I>(insets: I, ui: U) -> Self
+        where
+            I: Into<Insets<i32>>,
+    { Margin { insets: insets.into(), ui } }
+
+    pub fn uniform(margin: i32, ui: U) -> Self { Margin::new(Insets::uniform(margin), ui) }
+
+    pub fn symmetric(horizontal: i32, vertical: i32, ui: U) -> Self {
+        Margin::new(Insets::symmetric(horizontal, vertical), ui)
+    }
+
+    pub fn insets(&self) -> Insets<i32> { self.insets }
+}
+
+impl<U> Draw for Margin<U>
+    where
+        U: Draw,
+{
+    fn draw(&self, render: &mut Render, mut params: DrawParameters) {
+        params.frame = params.frame.inset(self.insets);
+        self.ui.draw(render, params);
+    }
+
+    fn measure(&self, metrics: &Metrics, params: &DrawParameters) -> Vec2d<i32> {
+        let mut inner = *params;
+        inner.frame = params.frame.inset(self.insets);
+
+        self.ui.measure(metrics, &inner) + self.insets.size()
+    }
+
+    fn hit(&self, hits: &mut HitTest, mut params: DrawParameters) {
+        params.frame = params.frame.inset(self.insets);
+        self.ui.hit(hits, params);
+    }
+}
+
+impl<U> std::ops::Deref for Margin<U> {
+    type Target = U;
+
+    fn deref(&self) -> &Self::Target { &self.ui }
+}
+
+impl<U> std::ops::DerefMut for Margin<U> {
+    fn deref_mut(&mut self) -> &mut Self::Target { &mut self.ui }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        common::Rect,
+        ui::{Padding, Pos, Row},
+    };
+
+    fn rects<U>(ui: U) -> Vec<Rect<i32>>
+        where
+            U: Draw,
+    {
+        HitTest::run(&Metrics::test(), &ui, Rect::new((0, 0), (100, 50)))
+            .iter()
+            .map(|hit| hit.rect)
+            .collect()
+    }
+
+    #[test]
+    fn measure() {
+        let params = DrawParameters::new(Rect::new((0, 0), (100, 50)));
+        let ui = Margin::new((1, 2, 3, 4), "a");
+
+        assert_eq!(ui.measure(&Metrics::test(), &params), Vec2d::new(8, 13));
+    }
+
+    #[test]
+    fn shrinks_frame() {
+        // The content keeps its position in the smaller frame
+        let ui = Margin::new((1, 2, 3, 4), Pos::right_top(0, 0, "a"));
+        assert_eq!(rects(ui), vec![Rect::new((93, 40), (5, 6))]);
+
+        let ui = Margin::new((1, 2, 3, 4), Rect::new((0, 0), (2, 2)));
+        assert_eq!(rects(ui), vec![Rect::new((1, 3), (2, 2))]);
+
+        // The padded box is centered, then the content is at its corner
+        let ui = Padding::uniform(4, Pos::right_top(0, 0, "a"));
+        assert_eq!(rects(ui), vec![Rect::new((48, 22), (5, 6))]);
+
+        let ui = Margin::uniform(4, Pos::right_top(0, 0, "a"));
+        assert_eq!(rects(ui), vec![Rect::new((91, 40), (5, 6))]);
+    }
+
+    #[test]
+    fn takes_space() {
+        let row = Row::new().with(Margin::symmetric(2, 0, "a")).with("b");
+        assert_eq!(rects(Pos::left_bot(0, 0, row)), vec![
+            Rect::new((2, 0), (5, 6)),
+            Rect::new((9, 0), (5, 6)),
+        ]);
+    }
+}
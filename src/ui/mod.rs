@@ -1,9 +1,51 @@
+mod button;
+mod checkbox;
+mod children;
+mod clip;
 mod col;
+mod column;
+mod flex;
+mod focus;
 mod font;
+mod frame;
+mod grid;
+mod id;
 mod image;
+mod margin;
+mod opacity;
+mod padding;
 mod pos;
+mod press;
+mod progress;
+mod radio;
+mod row;
+mod scroll;
+mod slider;
+mod stack;
+mod text_edit;
+mod text_input;
+mod transform;
 
+pub use button::{Button, ButtonState, ButtonStyle};
+pub use checkbox::Checkbox;
+pub use clip::Clip;
 pub use col::Col;
+pub use column::Column;
+pub use flex::{Flex, FlexItem, Justify, CrossAlign};
 pub use font::Font;
+pub use frame::{Frame, Area};
+pub use grid::{Grid, GridCell, Track};
+pub use id::Id;
 pub use image::Image;
+pub use margin::Margin;
+pub use opacity::Opacity;
+pub use padding::Padding;
 pub use pos::Pos;
+pub use progress::ProgressBar;
+pub use radio::RadioGroup;
+pub use row::Row;
+pub use scroll::Scroll;
+pub use slider::Slider;
+pub use stack::Stack;
+pub use text_input::TextInput;
+pub use transform::{Transform, Scale, Rotate};
@@ -0,0 +1,99 @@
+use crate::{
+    common::{
+        Color,
+        Vec2d,
+    },
+    graphics::{
+        Render,
+        Draw,
+        DrawParameters,
+        Metrics,
+        HitTest,
+    },
+};
+
+/// Fades or tints the whole content, including children with their own `Col`.
+#[derive(Debug)]
+pub struct Opacity<U> {
+    tint: Color,
+    ui: U,
+}
+
+#[allow(dead_code)]
+impl<U> Opacity<U>
+    where
+        U: Draw,
+{
+    pub fn new(opacity: f32, ui: U) -> Self { Opacity::tint(Color::white().with_alpha(opacity), ui) }
+
+    pub fn tint(tint: Color, ui: U) -> Self { Opacity { tint, ui } }
+
+    /// The colour that multiplies colours of the content.
+    pub fn color(&self) -> Color { self.tint }
+
+    pub fn opacity(&self) -> f32 { self.tint.a() }
+
+    pub fn set_opacity(&mut self, opacity: f32) { self.tint = self.tint.with_alpha(opacity) }
+}
+
+impl<U> Draw for Opacity<U>
+    where
+        U: Draw,
+{
+    fn draw(&self, render: &mut Render, params: DrawParameters) {
+        render.push_tint(self.tint);
+        self.ui.draw(render, params);
+        render.pop_tint();
+    }
+
+    fn measure(&self, metrics: &Metrics, params: &DrawParameters) -> Vec2d<i32> {
+        self.ui.measure(metrics, params)
+    }
+
+    fn hit(&self, hits: &mut HitTest, params: DrawParameters) { self.ui.hit(hits, params) }
+}
+
+impl<U> std::ops::Deref for Opacity<U> {
+    type Target = U;
+
+    fn deref(&self) -> &Self::Target { &self.ui }
+}
+
+impl<U> std::ops::DerefMut for Opacity<U> {
+    fn deref_mut(&mut self) -> &mut Self::Target { &mut self.ui }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        common::Rect,
+        ui::Pos,
+    };
+
+    #[test]
+    fn opacity() {
+        assert_eq!(Opacity::new(0.8, "a").opacity(), 0.8);
+
+        let orange = Color::rgb(1.0, 0.5, 0.0);
+        let mut ui = Opacity::tint(orange.with_alpha(0.5), "a");
+        assert_eq!(ui.opacity(), 0.5);
+
+        // The tint colour is kept
+        ui.set_opacity(0.25);
+        assert_eq!(ui.color(), orange.with_alpha(0.25));
+    }
+
+    #[test]
+    fn keeps_layout() {
+        let frame = Rect::new((0, 0), (100, 50));
+        let ui = Pos::right_top(1, 2, "ab");
+        let faded = Opacity::new(0.5, Pos::right_top(1, 2, "ab"));
+
+        let params = DrawParameters::new(frame);
+        assert_eq!(faded.measure(&Metrics::test(), &params), ui.measure(&Metrics::test(), &params));
+
+        let hits = HitTest::run(&Metrics::test(), &faded, frame);
+        assert_eq!(hits.at((95, 45)).unwrap().rect, Rect::new((89, 42), (10, 6)));
+    }
+}
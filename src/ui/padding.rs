@@ -0,0 +1,120 @@
+use crate::{
+    common::{
+        Insets,
+        Position,
+        Vec2d,
+    },
+    graphics::{
+        Render,
+        Draw,
+        DrawParameters,
+        Metrics,
+        HitTest,
+    },
+};
+
+/// Adds space around the content.
+///
+/// The padded box is positioned in the frame with the inherited `Position`,
+/// and the content is centered inside of it.
+#[derive(Debug)]
+pub struct Padding<U> {
+    insets: Insets<i32>,
+    ui: U,
+}
+
+#[allow(dead_code)]
+impl<U> Padding<U>
+    where
+        U: Draw,
+{
+    pub fn new<I>(insets: I, ui: U) -> Self
+        where
+            I: Into<Insets<i32>>,
+    { Padding { insets: insets.into(), ui } }
+
+    pub fn uniform(pad: i32, ui: U) -> Self { Padding::new(Insets::uniform(pad), ui) }
+
+    pub fn symmetric(horizontal: i32, vertical: i32, ui: U) -> Self {
+        Padding::new(Insets::symmetric(horizontal, vertical), ui)
+    }
+
+    pub fn insets(&self) -> Insets<i32> { self.insets }
+
+    fn inner_params(&self, metrics: &Metrics, mut params: DrawParameters) -> DrawParameters {
+        let size = self.measure(metrics, &params);
+
+        params.frame = params.render_rect(size).inset(self.insets);
+        params.position = Position::default();
+        params
+    }
+}
+
+impl<U> Draw for Padding<U>
+    where
+        U: Draw,
+{
+    fn draw(&self, render: &mut Render, params: DrawParameters) {
+        let params = self.inner_params(render.metrics(), params);
+        self.ui.draw(render, params);
+    }
+
+    fn measure(&self, metrics: &Metrics, params: &DrawParameters) -> Vec2d<i32> {
+        let mut inner = *params;
+        inner.frame = params.frame.inset(self.insets);
+
+        self.ui.measure(metrics, &inner) + self.insets.size()
+    }
+
+    fn hit(&self, hits: &mut HitTest, params: DrawParameters) {
+        let params = self.inner_params(hits.metrics(), params);
+        self.ui.hit(hits, params);
+    }
+}
+
+impl<U> std::ops::Deref for Padding<U> {
+    type Target = U;
+
+    fn deref(&self) -> &Self::Target { &self.ui }
+}
+
+impl<U> std::ops::DerefMut for Padding<U> {
+    fn deref_mut(&mut self) -> &mut Self::Target { &mut self.ui }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        common::Rect,
+        ui::Pos,
+    };
+
+    fn rects<U>(ui: U) -> Vec<Rect<i32>>
+        where
+            U: Draw,
+    {
+        HitTest::run(&Metrics::test(), &ui, Rect::new((0, 0), (100, 50)))
+            .iter()
+            .map(|hit| hit.rect)
+            .collect()
+    }
+
+    #[test]
+    fn measure() {
+        let params = DrawParameters::new(Rect::new((0, 0), (100, 50)));
+        let ui = Padding::new((1, 2, 3, 4), "a");
+
+        assert_eq!(ui.measure(&Metrics::test(), &params), Vec2d::new(8, 13));
+    }
+
+    #[test]
+    fn inner_frame() {
+        // The padded box is placed in the corner, the content is inside of it
+        let ui = Pos::right_top(0, 0, Padding::new((1, 2, 3, 4), "a"));
+        assert_eq!(rects(ui), vec![Rect::new((93, 40), (5, 6))]);
+
+        let ui = Pos::left_bot(0, 0, Padding::uniform(3, Rect::new((0, 0), (2, 2))));
+        assert_eq!(rects(ui), vec![Rect::new((3, 3), (2, 2))]);
+    }
+}
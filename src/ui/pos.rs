@@ -1,9 +1,14 @@
 use crate::{
-    common::Position,
+    common::{
+        Position,
+        Vec2d,
+    },
     graphics::{
         Render,
         Draw,
         DrawParameters,
+        Metrics,
+        HitTest,
     },
 };
 
@@ -30,6 +35,14 @@ impl<U> Pos<U>
     pub fn left_top(l: i32, t: i32, ui: U) -> Self { Pos::new(Position::LeftTop(l, t), ui) }
     pub fn right_bot(r: i32, b: i32, ui: U) -> Self { Pos::new(Position::RightBot(r, b), ui) }
     pub fn right_top(r: i32, t: i32, ui: U) -> Self { Pos::new(Position::RightTop(r, t), ui) }
+
+    pub fn anchored<A, P>(anchor: A, pivot: P, ui: U) -> Self
+        where
+            A: Into<Vec2d<f32>>,
+            P: Into<Vec2d<f32>>,
+    { Pos::new(Position::anchored(anchor, pivot), ui) }
+
+    pub fn percent(x: f32, y: f32, ui: U) -> Self { Pos::new(Position::percent(x, y), ui) }
 }
 
 impl<U> Draw for Pos<U>
@@ -40,6 +53,15 @@ impl<U> Draw for Pos<U>
         params.position = self.position;
         self.ui.draw(render, params);
     }
+
+    fn measure(&self, metrics: &Metrics, params: &DrawParameters) -> Vec2d<i32> {
+        self.ui.measure(metrics, params)
+    }
+
+    fn hit(&self, hits: &mut HitTest, mut params: DrawParameters) {
+        params.position = self.position;
+        self.ui.hit(hits, params);
+    }
 }
 
 impl<U> std::ops::Deref for Pos<U> {
@@ -0,0 +1,51 @@
+use std::cell::Cell;
+
+use crate::{
+    common::Rect,
+    input::{
+        Event,
+        Input,
+        MouseButton,
+    },
+};
+
+/// Tracks the left mouse button over a widget. The click is the press and the release over it.
+#[derive(Debug, Default)]
+pub(super) struct Press {
+    pressed: Cell<bool>,
+}
+
+impl Press {
+    /// Handles events of the frame, when the widget occupies the `rect`
+    /// in coordinates of the `input`.
+    /// Returns `true` if the widget was clicked.
+    pub(super) fn update(&self, input: &Input, rect: Rect<i32>) -> bool {
+        let mut clicked = false;
+
+        for event in input.events() {
+            match event {
+                Event::MouseDown(MouseButton::Left, pos) if rect.intersects_point(pos) => {
+                    self.pressed.set(true)
+                }
+                Event::MouseUp(MouseButton::Left, pos) => {
+                    let pressed = self.pressed.replace(false);
+                    clicked |= pressed && rect.intersects_point(pos);
+                }
+                _ => (),
+            }
+        }
+
+        clicked
+    }
+
+    pub(super) fn is_pressed(&self) -> bool { self.pressed.get() }
+
+    pub(super) fn reset(&self) { self.pressed.set(false) }
+}
+
+/// Checks the cursor is over the rect.
+pub(super) fn is_hovered(input: &Input, rect: Rect<i32>) -> bool {
+    input
+        .mouse()
+        .is_some_and(|pos| rect.intersects_point(pos))
+}
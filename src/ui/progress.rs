@@ -0,0 +1,114 @@
+use std::cell::Cell;
+
+use super::Image;
+use crate::{
+    common::{
+        Color,
+        Rect,
+        Vec2d,
+    },
+    graphics::{
+        Render,
+        Draw,
+        DrawParameters,
+        Metrics,
+    },
+};
+
+/// Shows the progress from 0 to 1, filling from the left. The fill has the inherited colour.
+#[derive(Debug)]
+pub struct ProgressBar<'a> {
+    size: Vec2d<i32>,
+    background: Color,
+    skin: Option<(&'a Image, &'a Image)>,
+    value: Cell<f32>,
+}
+
+#[allow(dead_code)]
+impl<'a> ProgressBar<'a> {
+    pub fn new<S>(size: S) -> Self
+        where
+            S: Into<Vec2d<i32>>,
+    {
+        ProgressBar {
+            size: size.into(),
+            background: Color::rgb(0.25, 0.25, 0.25),
+            skin: None,
+            value: Cell::new(0.0),
+        }
+    }
+
+    pub fn with_value(self, value: f32) -> Self {
+        self.set_value(value);
+        self
+    }
+
+    pub fn background(mut self, color: Color) -> Self {
+        self.background = color;
+        self
+    }
+
+    /// Draws images instead of rects. The `fill` is cut, not stretched.
+    pub fn skin(mut self, background: &'a Image, fill: &'a Image) -> Self {
+        self.skin = Some((background, fill));
+        self
+    }
+
+    pub fn value(&self) -> f32 { self.value.get() }
+
+    pub fn set_value(&self, value: f32) { self.value.set(value.clamp(0.0, 1.0)) }
+
+    /// Returns the filled part of the `rect` and its texture coordinates.
+    fn fill(&self, rect: Rect<i32>) -> (Rect<i32>, Rect<f32>) {
+        let width = (self.value() * rect.width as f32).round() as i32;
+        let fill = Rect::new(rect.pos(), (width, rect.height));
+
+        // The empty bar has nothing to cut
+        let s = if rect.width > 0 { width as f32 / rect.width as f32 } else { 0.0 };
+
+        (fill, Rect::new((0.0, 0.0), (s, 1.0)))
+    }
+}
+
+impl Draw for ProgressBar<'_> {
+    fn draw(&self, render: &mut Render, params: DrawParameters) {
+        let rect = params.render_rect(self.size);
+        let (fill, st) = self.fill(rect);
+
+        match self.skin {
+            Some((background, image)) => {
+                background.draw_stretched(render, params.color, rect);
+                image.draw_part(render, params.color, fill, st);
+            }
+            None => {
+                render.fill_rect(rect, self.background);
+                render.fill_rect(fill, params.color);
+            }
+        }
+    }
+
+    fn measure(&self, _: &Metrics, _: &DrawParameters) -> Vec2d<i32> { self.size }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fill() {
+        let bar = ProgressBar::new((11, 3)).with_value(0.5);
+        let (fill, st) = bar.fill(Rect::new((2, 1), (11, 3)));
+
+        assert_eq!(fill, Rect::new((2, 1), (6, 3)));
+        assert_eq!(st, Rect::new((0.0, 0.0), (6.0 / 11.0, 1.0)));
+
+        bar.set_value(2.0);
+        assert_eq!(bar.value(), 1.0);
+        assert_eq!(bar.fill(Rect::new((0, 0), (11, 3))).0.width, 11);
+
+        // The empty bar doesn't give NaN coordinates
+        let (fill, st) = bar.fill(Rect::new((0, 0), (0, 3)));
+        assert_eq!(fill.width, 0);
+        assert_eq!(st.width, 0.0);
+    }
+}
@@ -0,0 +1,287 @@
+use std::cell::Cell;
+
+use super::{
+    checkbox::draw_mark,
+    children::Children,
+    column,
+    focus::Focusable,
+    press::{self, Press},
+    Image,
+};
+use crate::{
+    common::{
+        Position,
+        Rect,
+        Vec2d,
+    },
+    graphics::{
+        Render,
+        Draw,
+        DrawParameters,
+        Metrics,
+    },
+    input::{
+        Event,
+        Input,
+        Key,
+        TabStop,
+    },
+};
+
+/// Options from top to bottom, only one of them can be selected.
+/// The mark of the selected option has the inherited colour.
+/// Arrows select the previous or the next option while the group is focused.
+pub struct RadioGroup<'a> {
+    options: Children<'a>,
+    presses: Vec<Press>,
+    size: i32,
+    spacing: i32,
+    label_gap: i32,
+    skin: Option<(&'a Image, &'a Image)>,
+    selected: Cell<Option<usize>>,
+    changed: Cell<bool>,
+    focus: Focusable,
+}
+
+#[allow(dead_code)]
+impl<'a> RadioGroup<'a> {
+    pub fn new() -> Self {
+        RadioGroup {
+            options: Children::new(),
+            presses: vec![],
+            size: 7,
+            spacing: 1,
+            label_gap: 2,
+            skin: None,
+            selected: Cell::new(None),
+            changed: Cell::new(false),
+            focus: Focusable::default(),
+        }
+    }
+
+    /// Adds the option with the label.
+    pub fn with<D>(mut self, label: D) -> Self
+        where
+            D: Draw + 'a,
+    {
+        self.push(label);
+        self
+    }
+
+    pub fn push<D>(&mut self, label: D)
+        where
+            D: Draw + 'a,
+    {
+        self.options.push(label);
+        self.presses.push(Press::default());
+    }
+
+    pub fn select(self, index: usize) -> Self {
+        self.set_selected(Some(index));
+        self
+    }
+
+    /// Sets the side of the mark.
+    pub fn size(mut self, size: i32) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Sets the space between options.
+    pub fn spacing(mut self, spacing: i32) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    /// Sets the space between the mark and the label of each option.
+    pub fn label_gap(mut self, gap: i32) -> Self {
+        self.label_gap = gap;
+        self
+    }
+
+    /// Draws images instead of marks.
+    pub fn skin(mut self, unselected: &'a Image, selected: &'a Image) -> Self {
+        self.skin = Some((unselected, selected));
+        self
+    }
+
+    /// Sets the order of Tab stops. Negative groups are skipped by Tab.
+    pub fn tab_index(mut self, index: i32) -> Self {
+        self.focus.set_stop(TabStop { index, ..self.focus.stop() });
+        self
+    }
+
+    pub fn selected(&self) -> Option<usize> { self.selected.get() }
+
+    pub fn set_selected(&self, index: Option<usize>) {
+        self.selected.set(index.filter(|&i| i < self.len()))
+    }
+
+    /// Whether the selection was changed at the last drawing.
+    pub fn changed(&self) -> bool { self.changed.get() }
+
+    pub fn is_focused(&self) -> bool { self.focus.is_focused() }
+
+    pub fn set_focused(&self, focused: bool) { self.focus.request(focused) }
+
+    pub fn len(&self) -> usize { self.options.len() }
+
+    pub fn is_empty(&self) -> bool { self.options.is_empty() }
+
+    /// Sizes of options with marks, they are placed as in the `Column`.
+    fn sizes(&self, metrics: &Metrics, params: &DrawParameters) -> Vec<Vec2d<i32>> {
+        self.options
+            .sizes(metrics, params)
+            .into_iter()
+            .map(|label| Vec2d::new(self.size + self.label_gap + label.x, self.size.max(label.y)))
+            .collect()
+    }
+
+    /// Returns the rect of the group and rows of options from top to bottom.
+    fn rows(&self, metrics: &Metrics, params: &DrawParameters) -> (Rect<i32>, Vec<Rect<i32>>) {
+        let sizes = self.sizes(metrics, params);
+        let rect = params.render_rect(column::total_size(&sizes, self.spacing));
+
+        (rect, column::frames(rect, &sizes, self.spacing))
+    }
+
+    /// Selects the option by the input, when the group occupies the `rect`.
+    fn update(&self, input: &Input, rect: Rect<i32>, rows: &[Rect<i32>]) {
+        self.focus.click(input, rect);
+
+        let selected = self.selected();
+        self.handle_keys(input);
+
+        for (i, (press, row)) in self.presses.iter().zip(rows).enumerate() {
+            if press.update(input, *row) {
+                self.selected.set(Some(i));
+            }
+        }
+
+        self.changed.set(self.selected() != selected);
+    }
+
+    /// Selects options by arrows.
+    fn handle_keys(&self, input: &Input) {
+        if !self.focus.is_focused() || self.is_empty() {
+            return;
+        }
+
+        let last = self.len() - 1;
+
+        for event in input.events() {
+            let selected = match (event, self.selected()) {
+                (Event::KeyDown(Key::Up), Some(i)) | (Event::KeyDown(Key::Left), Some(i)) => {
+                    i.saturating_sub(1)
+                }
+                (Event::KeyDown(Key::Down), Some(i)) | (Event::KeyDown(Key::Right), Some(i)) => {
+                    (i + 1).min(last)
+                }
+                (Event::KeyDown(Key::Up), None) | (Event::KeyDown(Key::Left), None) => last,
+                (Event::KeyDown(Key::Down), None) | (Event::KeyDown(Key::Right), None) => 0,
+                _ => continue,
+            };
+
+            self.selected.set(Some(selected));
+        }
+    }
+}
+
+impl Default for RadioGroup<'_> {
+    fn default() -> Self { RadioGroup::new() }
+}
+
+impl Draw for RadioGroup<'_> {
+    fn draw(&self, render: &mut Render, params: DrawParameters) {
+        let (rect, rows) = self.rows(render.metrics(), &params);
+
+        let slot = self.focus.begin(render, true);
+        self.update(render.input(), rect, &rows);
+
+        for (i, &row) in rows.iter().enumerate() {
+            let hovered = press::is_hovered(render.input(), row);
+            let on = self.selected() == Some(i);
+            draw_mark(render, &params, self.skin, row, self.size, hovered, on);
+        }
+
+        let label = self.size + self.label_gap;
+        let labels = rows.iter().map(|row| DrawParameters {
+            frame: Rect::new((row.x + label, row.y), (row.width - label, row.height)),
+            position: Position::Left(0),
+            ..params
+        });
+        self.options.draw(render, labels);
+
+        self.focus.end(render, slot, rect);
+    }
+
+    fn measure(&self, metrics: &Metrics, params: &DrawParameters) -> Vec2d<i32> {
+        column::total_size(&self.sizes(metrics, params), self.spacing)
+    }
+}
+
+impl std::fmt::Debug for RadioGroup<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RadioGroup")
+            .field("options", &self.options.len())
+            .field("selected", &self.selected.get())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::MouseButton;
+
+    fn group() -> RadioGroup<'static> { RadioGroup::new().with("a").with("ab").label_gap(3) }
+
+    fn frame(group: &RadioGroup, events: &[Event]) {
+        let params = DrawParameters {
+            position: Position::LeftBot(0, 0),
+            ..DrawParameters::new(Rect::new((0, 0), (100, 50)))
+        };
+        let (rect, rows) = group.rows(&Metrics::test(), &params);
+
+        let mut input = Input::new(100, 1);
+        events.iter().for_each(|&event| input.push(event));
+
+        group.update(&input, rect, &rows);
+    }
+
+    #[test]
+    fn rows() {
+        let params = DrawParameters {
+            position: Position::LeftBot(0, 0),
+            ..DrawParameters::new(Rect::new((0, 0), (100, 50)))
+        };
+        let (rect, rows) = group().spacing(2).rows(&Metrics::test(), &params);
+
+        assert_eq!(rect, Rect::new((0, 0), (20, 16)));
+        assert_eq!(rows, vec![Rect::new((0, 9), (20, 7)), Rect::new((0, 0), (20, 7))]);
+    }
+
+    #[test]
+    fn selection() {
+        let group = group();
+        let second = Vec2d::new(2, 2);
+
+        frame(&group, &[Event::MouseDown(MouseButton::Left, second)]);
+        frame(&group, &[Event::MouseUp(MouseButton::Left, second)]);
+        assert_eq!(group.selected(), Some(1));
+        assert!(group.changed());
+
+        frame(&group, &[]);
+        assert!(!group.changed());
+
+        // The click has focused the group
+        frame(&group, &[Event::KeyDown(Key::Up), Event::KeyDown(Key::Up)]);
+        assert_eq!(group.selected(), Some(0));
+
+        frame(&group, &[Event::KeyDown(Key::Down), Event::KeyDown(Key::Down)]);
+        assert_eq!(group.selected(), Some(1));
+
+        group.set_selected(Some(2));
+        assert_eq!(group.selected(), None);
+    }
+}
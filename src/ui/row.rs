@@ -0,0 +1,167 @@
+use super::children::Children;
+use crate::{
+    common::{
+        Align,
+        Position,
+        Rect,
+        Vec2d,
+    },
+    graphics::{
+        Render,
+        Draw,
+        DrawParameters,
+        Metrics,
+        HitTest,
+    },
+};
+
+/// Places children from left to right.
+#[derive(Debug)]
+pub struct Row<'a> {
+    spacing: i32,
+    align: Align,
+    children: Children<'a>,
+}
+
+#[allow(dead_code)]
+impl<'a> Row<'a> {
+    pub fn new() -> Self {
+        Row {
+            spacing: 0,
+            align: Align::default(),
+            children: Children::new(),
+        }
+    }
+
+    pub fn spacing(mut self, spacing: i32) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    /// Sets the vertical alignment of children.
+    pub fn align(mut self, align: Align) -> Self {
+        self.align = align;
+        self
+    }
+
+    pub fn with<D>(mut self, child: D) -> Self
+        where
+            D: Draw + 'a,
+    {
+        self.children.push(child);
+        self
+    }
+
+    pub fn push<D>(&mut self, child: D)
+        where
+            D: Draw + 'a,
+    { self.children.push(child) }
+
+    pub fn len(&self) -> usize { self.children.len() }
+
+    pub fn is_empty(&self) -> bool { self.children.is_empty() }
+
+    fn child_position(&self) -> Position {
+        match self.align {
+            Align::Start => Position::Top(0),
+            Align::Center => Position::Center,
+            Align::End => Position::Bot(0),
+        }
+    }
+
+    fn sizes(&self, metrics: &Metrics, params: &DrawParameters) -> (Vec<Vec2d<i32>>, Vec2d<i32>) {
+        let sizes = self.children.sizes(metrics, params);
+
+        let spaces = (sizes.len() as i32 - 1).max(0);
+        let total = sizes
+            .iter()
+            .fold(Vec2d::new(spaces * self.spacing, 0), |total, size| {
+                Vec2d::new(total.x + size.x, total.y.max(size.y))
+            });
+
+        (sizes, total)
+    }
+
+    fn child_params(&self, metrics: &Metrics, params: DrawParameters) -> Vec<DrawParameters> {
+        let (sizes, total) = self.sizes(metrics, &params);
+        let rect = params.render_rect(total);
+
+        let mut child_params = params;
+        child_params.position = self.child_position();
+
+        let mut x = rect.x;
+        sizes
+            .into_iter()
+            .map(|size| {
+                child_params.frame = Rect::new((x, rect.y), (size.x, rect.height));
+                x += size.x + self.spacing;
+                child_params
+            })
+            .collect()
+    }
+}
+
+impl Default for Row<'_> {
+    fn default() -> Self { Row::new() }
+}
+
+impl Draw for Row<'_> {
+    fn draw(&self, render: &mut Render, params: DrawParameters) {
+        let children = self.child_params(render.metrics(), params);
+        self.children.draw(render, children);
+    }
+
+    fn measure(&self, metrics: &Metrics, params: &DrawParameters) -> Vec2d<i32> {
+        self.sizes(metrics, params).1
+    }
+
+    fn hit(&self, hits: &mut HitTest, params: DrawParameters) {
+        let children = self.child_params(hits.metrics(), params);
+        self.children.hit(hits, children);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::Pos;
+
+    fn row() -> Row<'static> {
+        Row::new()
+            .spacing(2)
+            .with(Rect::new((0, 0), (10, 20)))
+            .with("a")
+            .with(Rect::new((0, 0), (3, 3)))
+    }
+
+    fn rects(row: Row) -> Vec<Rect<i32>> {
+        let ui = Pos::left_bot(0, 0, row);
+
+        HitTest::run(&Metrics::test(), &ui, Rect::new((0, 0), (100, 50)))
+            .iter()
+            .map(|hit| hit.rect)
+            .collect()
+    }
+
+    #[test]
+    fn spacing() {
+        let params = DrawParameters::new(Rect::new((0, 0), (100, 50)));
+        assert_eq!(row().measure(&Metrics::test(), &params), Vec2d::new(22, 20));
+        assert_eq!(Row::new().spacing(2).measure(&Metrics::test(), &params), Vec2d::new(0, 0));
+
+        assert_eq!(rects(row()), vec![
+            Rect::new((0, 0), (10, 20)),
+            Rect::new((12, 14), (5, 6)),
+            Rect::new((19, 0), (3, 3)),
+        ]);
+    }
+
+    #[test]
+    fn align() {
+        let text = |align| rects(row().align(align))[1];
+
+        assert_eq!(text(Align::Start), Rect::new((12, 14), (5, 6)));
+        assert_eq!(text(Align::Center), Rect::new((12, 7), (5, 6)));
+        assert_eq!(text(Align::End), Rect::new((12, 0), (5, 6)));
+    }
+}
@@ -0,0 +1,372 @@
+use std::cell::Cell;
+
+use super::press;
+use crate::{
+    common::{
+        Color,
+        Position,
+        Rect,
+        Vec2d,
+    },
+    graphics::{
+        Render,
+        Draw,
+        DrawParameters,
+        Metrics,
+        HitTest,
+    },
+    input::{
+        Event,
+        Input,
+        MouseButton,
+    },
+};
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Axis {
+    X,
+    Y,
+}
+
+/// Shows the part of the content in the viewport of the fixed size.
+///
+/// The content is scrolled by the wheel over it and by dragging scrollbars.
+/// Scrollbars appear only when the content doesn't fit. The thumb has the inherited colour.
+#[derive(Debug)]
+pub struct Scroll<U> {
+    ui: U,
+    size: Vec2d<i32>,
+    horizontal: bool,
+    vertical: bool,
+    bar: i32,
+    line: i32,
+    /// The offset of the viewport from the top left corner of the content.
+    offset: Cell<Vec2d<i32>>,
+    /// The axis and the offset of the cursor from the thumb start.
+    drag: Cell<Option<(Axis, i32)>>,
+}
+
+/// Where the parts of the scroll are.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+struct ScrollLayout {
+    rect: Rect<i32>,
+    viewport: Rect<i32>,
+    content: Vec2d<i32>,
+    /// Tracks of scrollbars, if they are shown.
+    bar_x: Option<Rect<i32>>,
+    bar_y: Option<Rect<i32>>,
+}
+
+impl ScrollLayout {
+    fn max_offset(&self) -> Vec2d<i32> {
+        (self.content - self.viewport.size()).max(Vec2d::new(0, 0))
+    }
+
+    /// Returns the thumb rect of the axis for the offset.
+    fn thumb(&self, axis: Axis, offset: Vec2d<i32>) -> Option<Rect<i32>> {
+        const MIN_THUMB: i32 = 4;
+
+        let max = self.max_offset();
+
+        match axis {
+            Axis::X => self.bar_x.map(|track| {
+                let len = (track.width * self.viewport.width / self.content.x).max(MIN_THUMB);
+                let pos = (track.width - len) * offset.x / max.x.max(1);
+
+                Rect::new((track.x + pos, track.y), (len, track.height))
+            }),
+            Axis::Y => self.bar_y.map(|track| {
+                let len = (track.height * self.viewport.height / self.content.y).max(MIN_THUMB);
+                let pos = (track.height - len) * offset.y / max.y.max(1);
+
+                Rect::new((track.x, track.top() - pos - len), (track.width, len))
+            }),
+        }
+    }
+
+    /// Returns the offset that places the thumb start at the `pos` along the track.
+    fn offset_at(&self, axis: Axis, pos: Vec2d<i32>, grab: i32, offset: Vec2d<i32>) -> Vec2d<i32> {
+        let max = self.max_offset();
+        let thumb = match self.thumb(axis, offset) {
+            Some(thumb) => thumb,
+            None => return offset,
+        };
+
+        match axis {
+            Axis::X => {
+                let track = self.bar_x.unwrap();
+                let free = (track.width - thumb.width).max(1);
+                let x = (pos.x - grab - track.x) * max.x / free;
+
+                Vec2d::new(x, offset.y)
+            }
+            Axis::Y => {
+                let track = self.bar_y.unwrap();
+                let free = (track.height - thumb.height).max(1);
+                let y = (track.top() - (pos.y + grab)) * max.y / free;
+
+                Vec2d::new(offset.x, y)
+            }
+        }
+    }
+}
+
+#[allow(dead_code)]
+impl<U> Scroll<U>
+    where
+        U: Draw,
+{
+    /// Creates the vertical scroll with the viewport of the `size`.
+    pub fn new<S>(size: S, ui: U) -> Self
+        where
+            S: Into<Vec2d<i32>>,
+    {
+        Scroll {
+            ui,
+            size: size.into(),
+            horizontal: false,
+            vertical: true,
+            bar: 2,
+            line: 8,
+            offset: Cell::new(Vec2d::default()),
+            drag: Cell::new(None),
+        }
+    }
+
+    pub fn horizontal(mut self, horizontal: bool) -> Self {
+        self.horizontal = horizontal;
+        self
+    }
+
+    pub fn vertical(mut self, vertical: bool) -> Self {
+        self.vertical = vertical;
+        self
+    }
+
+    /// Sets the thickness of scrollbars.
+    pub fn bar(mut self, bar: i32) -> Self {
+        self.bar = bar;
+        self
+    }
+
+    /// Sets the scroll by one wheel line, in pixels.
+    pub fn line(mut self, line: i32) -> Self {
+        self.line = line;
+        self
+    }
+
+    /// The offset from the top left corner of the content.
+    pub fn offset(&self) -> Vec2d<i32> { self.offset.get() }
+
+    /// Scrolls to the offset from the top left corner. It's clamped at the next drawing.
+    pub fn scroll_to<O>(&self, offset: O)
+        where
+            O: Into<Vec2d<i32>>,
+    { self.offset.set(offset.into()) }
+
+    pub fn scroll_by<D>(&self, delta: D)
+        where
+            D: Into<Vec2d<i32>>,
+    { self.scroll_to(self.offset() + delta.into()) }
+
+    fn layout(&self, rect: Rect<i32>, content: Vec2d<i32>) -> ScrollLayout {
+        let fits_x = |width| !self.horizontal || content.x <= width;
+        let fits_y = |height| !self.vertical || content.y <= height;
+
+        // A bar takes the space, so the other one may be needed
+        let mut bar_y = !fits_y(rect.height);
+        let bar_x = !fits_x(rect.width - if bar_y { self.bar } else { 0 });
+        bar_y = !fits_y(rect.height - if bar_x { self.bar } else { 0 });
+
+        let right = if bar_y { self.bar } else { 0 };
+        let bot = if bar_x { self.bar } else { 0 };
+        let viewport = Rect::new((rect.x, rect.y + bot), (rect.width - right, rect.height - bot));
+
+        ScrollLayout {
+            rect,
+            viewport,
+            content,
+            bar_x: Some(Rect::new((rect.x, rect.y), (viewport.width, bot))).filter(|_| bar_x),
+            bar_y: Some(Rect::new((viewport.right(), viewport.y), (right, viewport.height)))
+                .filter(|_| bar_y),
+        }
+    }
+
+    fn layout_params(&self, metrics: &Metrics, params: &DrawParameters) -> ScrollLayout {
+        let rect = params.render_rect(self.size);
+
+        let mut inner = *params;
+        inner.frame = rect;
+        let content = self.ui.measure(metrics, &inner);
+
+        self.layout(rect, content)
+    }
+
+    /// Returns parameters of the content scrolled by the offset.
+    fn child_params(&self, layout: &ScrollLayout, mut params: DrawParameters) -> DrawParameters {
+        let viewport = layout.viewport;
+        let offset = self.offset();
+        let size = layout.content.max(viewport.size());
+
+        params.frame = Rect::new(
+            (viewport.x - offset.x, viewport.top() - size.y + offset.y),
+            size,
+        );
+        params.position = Position::LeftTop(0, 0);
+        params
+    }
+
+    fn update(&self, input: &Input, layout: &ScrollLayout) {
+        let mut offset = self.offset();
+
+        for event in input.events() {
+            match event {
+                Event::Scroll(delta) if press::is_hovered(input, layout.rect) => {
+                    let delta = (delta * self.line as f32).cast::<i32>();
+
+                    if self.vertical {
+                        offset.x += delta.x;
+                        offset.y -= delta.y;
+                    } else {
+                        offset.x += delta.x - delta.y;
+                    }
+                }
+                Event::MouseDown(MouseButton::Left, pos) => {
+                    for &axis in &[Axis::X, Axis::Y] {
+                        let track = if axis == Axis::X { layout.bar_x } else { layout.bar_y };
+                        let thumb = layout.thumb(axis, offset);
+
+                        if let (Some(track), Some(thumb)) = (track, thumb) {
+                            if !track.intersects_point(pos) {
+                                continue;
+                            }
+
+                            // The click outside of the thumb moves its center to the cursor
+                            let grab = match (thumb.intersects_point(pos), axis) {
+                                (true, Axis::X) => pos.x - thumb.x,
+                                (true, Axis::Y) => thumb.top() - pos.y,
+                                (false, Axis::X) => thumb.width / 2,
+                                (false, Axis::Y) => thumb.height / 2,
+                            };
+
+                            self.drag.set(Some((axis, grab)));
+                            offset = layout.offset_at(axis, pos, grab, offset);
+                        }
+                    }
+                }
+                Event::MouseMove(pos) => {
+                    if let Some((axis, grab)) = self.drag.get() {
+                        offset = layout.offset_at(axis, pos, grab, offset);
+                    }
+                }
+                Event::MouseUp(MouseButton::Left, _) => self.drag.set(None),
+                _ => (),
+            }
+        }
+
+        self.offset.set(offset.clamp(Vec2d::new(0, 0), layout.max_offset()));
+    }
+}
+
+impl<U> Draw for Scroll<U>
+    where
+        U: Draw,
+{
+    fn draw(&self, render: &mut Render, params: DrawParameters) {
+        let layout = self.layout_params(render.metrics(), &params);
+
+        self.update(render.input(), &layout);
+
+        render.push_clip(layout.viewport);
+        self.ui.draw(render, self.child_params(&layout, params));
+        render.pop_clip();
+
+        for &(track, axis) in &[(layout.bar_x, Axis::X), (layout.bar_y, Axis::Y)] {
+            if let (Some(track), Some(thumb)) = (track, layout.thumb(axis, self.offset())) {
+                render.fill_rect(track, Color::rgb(0.2, 0.2, 0.2));
+                render.fill_rect(thumb, params.color);
+            }
+        }
+    }
+
+    fn measure(&self, _: &Metrics, _: &DrawParameters) -> Vec2d<i32> { self.size }
+
+    fn hit(&self, hits: &mut HitTest, params: DrawParameters) {
+        let layout = self.layout_params(hits.metrics(), &params);
+        hits.record(layout.rect);
+
+        hits.push_clip(layout.viewport);
+        self.ui.hit(hits, self.child_params(&layout, params));
+        hits.pop_clip();
+    }
+}
+
+impl<U> std::ops::Deref for Scroll<U> {
+    type Target = U;
+
+    fn deref(&self) -> &Self::Target { &self.ui }
+}
+
+impl<U> std::ops::DerefMut for Scroll<U> {
+    fn deref_mut(&mut self) -> &mut Self::Target { &mut self.ui }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scroll() -> Scroll<Rect<i32>> { Scroll::new((20, 10), Rect::new((0, 0), (0, 0))) }
+
+    #[test]
+    fn bars() {
+        let rect = Rect::new((0, 0), (20, 10));
+
+        let layout = scroll().layout(rect, Vec2d::new(20, 10));
+        assert_eq!(layout.viewport, rect);
+        assert_eq!((layout.bar_x, layout.bar_y), (None, None));
+
+        let layout = scroll().layout(rect, Vec2d::new(30, 30));
+        assert_eq!(layout.viewport, Rect::new((0, 0), (18, 10)));
+        assert_eq!(layout.bar_y, Some(Rect::new((18, 0), (2, 10))));
+        assert_eq!(layout.bar_x, None);
+
+        // The vertical bar makes the content wider than the viewport
+        let layout = scroll().horizontal(true).layout(rect, Vec2d::new(19, 30));
+        assert_eq!(layout.viewport, Rect::new((0, 2), (18, 8)));
+        assert_eq!(layout.bar_x, Some(Rect::new((0, 0), (18, 2))));
+        assert_eq!(layout.max_offset(), Vec2d::new(1, 22));
+    }
+
+    #[test]
+    fn thumb() {
+        let rect = Rect::new((0, 0), (20, 10));
+        let layout = scroll().layout(rect, Vec2d::new(18, 20));
+
+        // Half of the content is visible
+        let top = layout.thumb(Axis::Y, Vec2d::new(0, 0));
+        assert_eq!(top, Some(Rect::new((18, 5), (2, 5))));
+
+        let bot = layout.thumb(Axis::Y, Vec2d::new(0, 10));
+        assert_eq!(bot, Some(Rect::new((18, 0), (2, 5))));
+
+        let offset = layout.offset_at(Axis::Y, Vec2d::new(19, 7), 1, Vec2d::new(0, 0));
+        assert_eq!(offset, Vec2d::new(0, 4));
+    }
+
+    #[test]
+    fn wheel_and_scroll_to() {
+        let scroll = scroll();
+        let layout = scroll.layout(Rect::new((0, 0), (20, 10)), Vec2d::new(18, 100));
+
+        let mut input = Input::new(10, 1);
+        input.push(Event::MouseMove(Vec2d::new(5, 5)));
+        input.push(Event::Scroll(Vec2d::new(0.0, -2.0)));
+        scroll.update(&input, &layout);
+        assert_eq!(scroll.offset(), Vec2d::new(0, 16));
+
+        scroll.scroll_to((0, 1000));
+        input.end_frame();
+        scroll.update(&input, &layout);
+        assert_eq!(scroll.offset(), Vec2d::new(0, 90));
+    }
+}
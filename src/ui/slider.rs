@@ -0,0 +1,317 @@
+use std::cell::Cell;
+
+use super::{
+    focus::Focusable,
+    press,
+    Image,
+};
+use crate::{
+    common::{
+        Color,
+        Rect,
+        Vec2d,
+    },
+    graphics::{
+        Render,
+        Draw,
+        DrawParameters,
+        Metrics,
+    },
+    input::{
+        Event,
+        Input,
+        Key,
+        MouseButton,
+        TabStop,
+    },
+};
+
+/// The value in a range, changed by dragging the thumb, by the wheel
+/// and by arrow keys after a click. The thumb has the inherited colour.
+#[derive(Debug)]
+pub struct Slider<'a> {
+    min: f32,
+    max: f32,
+    step: Option<f32>,
+    length: i32,
+    thickness: i32,
+    thumb: i32,
+    vertical: bool,
+    skin: Option<(&'a Image, &'a Image)>,
+    value: Cell<f32>,
+    changed: Cell<bool>,
+    dragging: Cell<bool>,
+    focus: Focusable,
+}
+
+#[allow(dead_code)]
+impl<'a> Slider<'a> {
+    pub fn new(min: f32, max: f32) -> Self {
+        Slider {
+            min,
+            max: max.max(min),
+            step: None,
+            length: 50,
+            thickness: 5,
+            thumb: 3,
+            vertical: false,
+            skin: None,
+            value: Cell::new(min),
+            changed: Cell::new(false),
+            dragging: Cell::new(false),
+            focus: Focusable::default(),
+        }
+    }
+
+    /// Creates the slider of integer values.
+    pub fn int(min: i32, max: i32) -> Self { Slider::new(min as f32, max as f32).step(1.0) }
+
+    /// Snaps the value to multiples of the `step` from the minimum.
+    pub fn step(mut self, step: f32) -> Self {
+        self.step = Some(step).filter(|&step| step > 0.0);
+        self.set_value(self.value.get());
+        self
+    }
+
+    pub fn with_value(self, value: f32) -> Self {
+        self.set_value(value);
+        self
+    }
+
+    /// Sets the length and the thickness. For the vertical slider it's the height and the width.
+    pub fn size(mut self, length: i32, thickness: i32) -> Self {
+        self.length = length;
+        self.thickness = thickness;
+        self
+    }
+
+    /// Sets the length of the thumb along the slider.
+    pub fn thumb(mut self, thumb: i32) -> Self {
+        self.thumb = thumb;
+        self
+    }
+
+    pub fn vertical(mut self) -> Self {
+        self.vertical = true;
+        self
+    }
+
+    pub fn skin(mut self, track: &'a Image, thumb: &'a Image) -> Self {
+        self.skin = Some((track, thumb));
+        self
+    }
+
+    /// Sets the order of Tab stops. Negative sliders are skipped by Tab.
+    pub fn tab_index(mut self, index: i32) -> Self {
+        self.focus.set_stop(TabStop { index, ..self.focus.stop() });
+        self
+    }
+
+    pub fn value(&self) -> f32 { self.value.get() }
+
+    /// Returns the value rounded to the integer.
+    pub fn value_int(&self) -> i32 { self.value().round() as i32 }
+
+    pub fn set_value(&self, value: f32) { self.value.set(self.snap(value)) }
+
+    /// Whether the value was changed at the last drawing.
+    pub fn changed(&self) -> bool { self.changed.get() }
+
+    pub fn is_focused(&self) -> bool { self.focus.is_focused() }
+
+    pub fn set_focused(&self, focused: bool) { self.focus.request(focused) }
+
+    fn snap(&self, value: f32) -> f32 {
+        // Unlike `clamp`, doesn't panic on NaN bounds
+        let value = value.max(self.min).min(self.max);
+
+        match self.step {
+            Some(step) => (self.min + ((value - self.min) / step).round() * step).min(self.max),
+            None => value,
+        }
+    }
+
+    /// The change by arrow keys.
+    fn nudge(&self) -> f32 { self.step.unwrap_or((self.max - self.min) / 100.0) }
+
+    /// Returns the offset of the point along the slider and the free length of the track.
+    fn along(&self, rect: Rect<i32>, point: Vec2d<i32>) -> (i32, i32) {
+        if self.vertical {
+            (point.y - rect.y, rect.height - self.thumb)
+        } else {
+            (point.x - rect.x, rect.width - self.thumb)
+        }
+    }
+
+    fn value_at(&self, rect: Rect<i32>, point: Vec2d<i32>) -> f32 {
+        let (offset, free) = self.along(rect, point);
+        let t = if free > 0 { (offset - self.thumb / 2) as f32 / free as f32 } else { 0.0 };
+
+        self.min + t.clamp(0.0, 1.0) * (self.max - self.min)
+    }
+
+    fn thumb_rect(&self, rect: Rect<i32>) -> Rect<i32> {
+        let range = self.max - self.min;
+        let t = if range > 0.0 { (self.value() - self.min) / range } else { 0.0 };
+
+        if self.vertical {
+            let offset = (t * (rect.height - self.thumb) as f32).round() as i32;
+            Rect::new((rect.x, rect.y + offset), (rect.width, self.thumb))
+        } else {
+            let offset = (t * (rect.width - self.thumb) as f32).round() as i32;
+            Rect::new((rect.x + offset, rect.y), (self.thumb, rect.height))
+        }
+    }
+
+    /// Updates the value from the input, when the slider occupies the `rect`.
+    fn update(&self, input: &Input, rect: Rect<i32>) {
+        let old = self.value();
+
+        for event in input.events() {
+            match event {
+                Event::MouseDown(MouseButton::Left, pos) => {
+                    let inside = rect.intersects_point(pos);
+
+                    self.focus.set(inside);
+                    self.dragging.set(inside);
+
+                    if inside {
+                        self.set_value(self.value_at(rect, pos));
+                    }
+                }
+                Event::MouseMove(pos) if self.dragging.get() => {
+                    self.set_value(self.value_at(rect, pos))
+                }
+                Event::MouseUp(MouseButton::Left, _) => self.dragging.set(false),
+                Event::Scroll(delta) if press::is_hovered(input, rect) => {
+                    let delta = if self.vertical { delta.y } else { delta.x + delta.y };
+
+                    if delta != 0.0 {
+                        self.set_value(self.value() + delta.signum() * self.nudge());
+                    }
+                }
+                Event::KeyDown(key) if self.is_focused() => match key {
+                    Key::Left | Key::Down => self.set_value(self.value() - self.nudge()),
+                    Key::Right | Key::Up => self.set_value(self.value() + self.nudge()),
+                    Key::PageDown => self.set_value(self.value() - self.nudge() * 10.0),
+                    Key::PageUp => self.set_value(self.value() + self.nudge() * 10.0),
+                    Key::Home => self.set_value(self.min),
+                    Key::End => self.set_value(self.max),
+                    _ => (),
+                }
+                _ => (),
+            }
+        }
+
+        self.changed.set(self.value() != old);
+    }
+}
+
+impl Draw for Slider<'_> {
+    fn draw(&self, render: &mut Render, params: DrawParameters) {
+        let rect = params.render_rect(self.measure(render.metrics(), &params));
+
+        let slot = self.focus.begin(render, true);
+        self.update(render.input(), rect);
+        let thumb = self.thumb_rect(rect);
+
+        match self.skin {
+            Some((track, thumb_image)) => {
+                track.draw_stretched(render, params.color, rect);
+                thumb_image.draw_stretched(render, params.color, thumb);
+            }
+            None => {
+                let track = if self.vertical {
+                    Rect::new((rect.x + rect.width / 2 - 1, rect.y), (2, rect.height))
+                } else {
+                    Rect::new((rect.x, rect.y + rect.height / 2 - 1), (rect.width, 2))
+                };
+
+                render.fill_rect(track, Color::rgb(0.25, 0.25, 0.25));
+                render.fill_rect(thumb, params.color);
+            }
+        }
+
+        self.focus.end(render, slot, rect);
+    }
+
+    fn measure(&self, _: &Metrics, _: &DrawParameters) -> Vec2d<i32> {
+        if self.vertical {
+            Vec2d::new(self.thickness, self.length)
+        } else {
+            Vec2d::new(self.length, self.thickness)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snap() {
+        let slider = Slider::new(1.0, 2.1).step(0.25);
+
+        slider.set_value(1.4);
+        assert_eq!(slider.value(), 1.5);
+        slider.set_value(2.09);
+        assert_eq!(slider.value(), 2.0);
+        slider.set_value(5.0);
+        assert_eq!(slider.value(), 2.0);
+        slider.set_value(-5.0);
+        assert_eq!(slider.value(), 1.0);
+
+        let slider = Slider::int(0, 10).with_value(3.6);
+        assert_eq!(slider.value_int(), 4);
+
+        let slider = Slider::new(2.0, 1.0);
+        slider.set_value(5.0);
+        assert_eq!(slider.value(), 2.0);
+
+        let slider = Slider::new(f32::NAN, 1.0);
+        slider.set_value(0.5);
+        assert_eq!(slider.value(), 0.5);
+    }
+
+    #[test]
+    fn drag_and_keys() {
+        let slider = Slider::int(0, 10);
+        let rect = Rect::new((0, 0), (13, 5));
+
+        let mut input = Input::new(100, 1);
+        input.push(Event::MouseDown(MouseButton::Left, Vec2d::new(6, 2)));
+        slider.update(&input, rect);
+        assert_eq!(slider.value_int(), 5);
+        assert!(slider.changed());
+        assert_eq!(slider.thumb_rect(rect), Rect::new((5, 0), (3, 5)));
+
+        input.end_frame();
+        input.push(Event::MouseMove(Vec2d::new(50, 2)));
+        input.push(Event::MouseUp(MouseButton::Left, Vec2d::new(50, 2)));
+        slider.update(&input, rect);
+        assert_eq!(slider.value_int(), 10);
+
+        input.end_frame();
+        input.push(Event::KeyDown(Key::Left));
+        input.push(Event::KeyDown(Key::Left));
+        slider.update(&input, rect);
+        assert_eq!(slider.value_int(), 8);
+
+        input.end_frame();
+        input.push(Event::MouseDown(MouseButton::Left, Vec2d::new(50, 50)));
+        input.push(Event::KeyDown(Key::Left));
+        slider.update(&input, rect);
+        assert_eq!(slider.value_int(), 8);
+        assert!(!slider.changed());
+    }
+
+    #[test]
+    fn vertical() {
+        let slider = Slider::new(0.0, 1.0).vertical();
+        let rect = Rect::new((0, 0), (5, 23));
+
+        assert_eq!(slider.value_at(rect, Vec2d::new(0, 11)), 0.5);
+        assert_eq!(slider.value_at(rect, Vec2d::new(0, 0)), 0.0);
+        assert_eq!(slider.value_at(rect, Vec2d::new(0, 30)), 1.0);
+    }
+}
@@ -0,0 +1,118 @@
+use super::children::Children;
+use crate::{
+    common::{
+        Position,
+        Vec2d,
+    },
+    graphics::{
+        Render,
+        Draw,
+        DrawParameters,
+        Metrics,
+        HitTest,
+    },
+};
+
+/// Places children on top of each other. The first child is drawn first.
+#[derive(Debug)]
+pub struct Stack<'a> {
+    position: Position,
+    children: Children<'a>,
+}
+
+#[allow(dead_code)]
+impl<'a> Stack<'a> {
+    pub fn new() -> Self {
+        Stack {
+            position: Position::default(),
+            children: Children::new(),
+        }
+    }
+
+    /// Sets the position of children inside the stack.
+    pub fn position(mut self, position: Position) -> Self {
+        self.position = position;
+        self
+    }
+
+    pub fn with<D>(mut self, child: D) -> Self
+        where
+            D: Draw + 'a,
+    {
+        self.children.push(child);
+        self
+    }
+
+    pub fn push<D>(&mut self, child: D)
+        where
+            D: Draw + 'a,
+    { self.children.push(child) }
+
+    pub fn len(&self) -> usize { self.children.len() }
+
+    pub fn is_empty(&self) -> bool { self.children.is_empty() }
+
+    /// Children share the same parameters.
+    fn child_params(&self, metrics: &Metrics, params: DrawParameters) -> DrawParameters {
+        let size = self.measure(metrics, &params);
+
+        let mut child_params = params;
+        child_params.frame = params.render_rect(size);
+        child_params.position = self.position;
+        child_params
+    }
+}
+
+impl Default for Stack<'_> {
+    fn default() -> Self { Stack::new() }
+}
+
+impl Draw for Stack<'_> {
+    fn draw(&self, render: &mut Render, params: DrawParameters) {
+        let child_params = self.child_params(render.metrics(), params);
+        self.children.draw(render, std::iter::repeat(child_params));
+    }
+
+    fn measure(&self, metrics: &Metrics, params: &DrawParameters) -> Vec2d<i32> {
+        self.children
+            .sizes(metrics, params)
+            .into_iter()
+            .fold(Vec2d::default(), |total, size| {
+                Vec2d::new(total.x.max(size.x), total.y.max(size.y))
+            })
+    }
+
+    fn hit(&self, hits: &mut HitTest, params: DrawParameters) {
+        let child_params = self.child_params(hits.metrics(), params);
+        self.children.hit(hits, std::iter::repeat(child_params));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        common::Rect,
+        ui::Pos,
+    };
+
+    fn rects(stack: Stack) -> Vec<Rect<i32>> {
+        let ui = Pos::left_bot(0, 0, stack.with(Rect::new((0, 0), (10, 20))).with("a"));
+
+        HitTest::run(&Metrics::test(), &ui, Rect::new((0, 0), (100, 50)))
+            .iter()
+            .map(|hit| hit.rect)
+            .collect()
+    }
+
+    #[test]
+    fn position() {
+        assert_eq!(rects(Stack::new()), vec![
+            Rect::new((0, 0), (10, 20)),
+            Rect::new((3, 7), (5, 6)),
+        ]);
+
+        let right_top = rects(Stack::new().position(Position::RightTop(0, 0)));
+        assert_eq!(right_top[1], Rect::new((5, 14), (5, 6)));
+    }
+}
@@ -0,0 +1,312 @@
+use std::ops::Range;
+
+/// The editable text with the caret and the selection.
+///
+/// Positions are indices of chars, not bytes. The selection is between the `anchor` and the caret.
+#[derive(Clone, Debug, Default)]
+pub struct TextEdit {
+    text: String,
+    caret: usize,
+    anchor: usize,
+    multiline: bool,
+}
+
+#[allow(dead_code)]
+impl TextEdit {
+    /// Creates the single line text. New lines are removed.
+    pub fn new<S>(text: S) -> Self
+        where
+            S: Into<String>,
+    {
+        let mut edit = TextEdit::default();
+        edit.set_text(text);
+        edit
+    }
+
+    /// Creates the text that allows new lines.
+    pub fn multiline<S>(text: S) -> Self
+        where
+            S: Into<String>,
+    {
+        let mut edit = TextEdit {
+            multiline: true,
+            ..TextEdit::default()
+        };
+
+        edit.set_text(text);
+        edit
+    }
+
+    pub fn is_multiline(&self) -> bool { self.multiline }
+
+    pub fn text(&self) -> &str { self.text.as_str() }
+
+    /// Replaces the text and moves the caret to the end.
+    pub fn set_text<S>(&mut self, text: S)
+        where
+            S: Into<String>,
+    {
+        self.text = text.into();
+
+        if !self.multiline {
+            self.text.retain(|c| c != '\n');
+        }
+
+        self.caret = self.len();
+        self.anchor = self.caret;
+    }
+
+    /// The number of chars.
+    pub fn len(&self) -> usize { self.text.chars().count() }
+
+    pub fn is_empty(&self) -> bool { self.text.is_empty() }
+
+    pub fn caret(&self) -> usize { self.caret }
+
+    pub fn selection(&self) -> Option<Range<usize>> {
+        if self.caret == self.anchor {
+            None
+        } else {
+            Some(self.caret.min(self.anchor)..self.caret.max(self.anchor))
+        }
+    }
+
+    pub fn selected_text(&self) -> &str {
+        match self.selection() {
+            Some(range) => &self.text[self.byte_range(range)],
+            None => "",
+        }
+    }
+
+    pub fn select_all(&mut self) {
+        self.anchor = 0;
+        self.caret = self.len();
+    }
+
+    /// Moves the caret. With the `select` the selection is extended, otherwise it's dropped.
+    pub fn move_to(&mut self, index: usize, select: bool) {
+        self.caret = index.min(self.len());
+
+        if !select {
+            self.anchor = self.caret;
+        }
+    }
+
+    pub fn left(&mut self, select: bool) {
+        match self.selection() {
+            Some(range) if !select => self.move_to(range.start, false),
+            _ => self.move_to(self.caret.saturating_sub(1), select),
+        }
+    }
+
+    pub fn right(&mut self, select: bool) {
+        match self.selection() {
+            Some(range) if !select => self.move_to(range.end, false),
+            _ => self.move_to(self.caret + 1, select),
+        }
+    }
+
+    /// Moves the caret to the start of the word.
+    pub fn word_left(&mut self, select: bool) {
+        let chars: Vec<char> = self.text.chars().collect();
+
+        let mut index = self.caret;
+        while index > 0 && !is_word(chars[index - 1]) {
+            index -= 1;
+        }
+
+        while index > 0 && is_word(chars[index - 1]) {
+            index -= 1;
+        }
+
+        self.move_to(index, select)
+    }
+
+    /// Moves the caret to the end of the word.
+    pub fn word_right(&mut self, select: bool) {
+        let chars: Vec<char> = self.text.chars().collect();
+
+        let mut index = self.caret;
+        while index < chars.len() && !is_word(chars[index]) {
+            index += 1;
+        }
+
+        while index < chars.len() && is_word(chars[index]) {
+            index += 1;
+        }
+
+        self.move_to(index, select)
+    }
+
+    /// Moves the caret to the start of the line.
+    pub fn home(&mut self, select: bool) { self.move_to(self.line_start(self.caret), select) }
+
+    /// Moves the caret to the end of the line.
+    pub fn end(&mut self, select: bool) { self.move_to(self.line_end(self.caret), select) }
+
+    /// Returns the index of the first char of the line with the char at the `index`.
+    pub fn line_start(&self, index: usize) -> usize {
+        self.text
+            .chars()
+            .take(index)
+            .enumerate()
+            .filter(|&(_, c)| c == '\n')
+            .last()
+            .map_or(0, |(i, _)| i + 1)
+    }
+
+    /// Returns the index of the new line char or the end of the text.
+    pub fn line_end(&self, index: usize) -> usize {
+        self.text
+            .chars()
+            .enumerate()
+            .skip(index)
+            .find(|&(_, c)| c == '\n')
+            .map_or(self.len(), |(i, _)| i)
+    }
+
+    /// Replaces the selection with the text.
+    pub fn insert(&mut self, text: &str) {
+        self.delete_selection();
+
+        let text: String = if self.multiline {
+            text.into()
+        } else {
+            text.chars().filter(|&c| c != '\n').collect()
+        };
+
+        let at = self.byte_index(self.caret);
+        self.text.insert_str(at, text.as_str());
+        self.move_to(self.caret + text.chars().count(), false);
+    }
+
+    /// Deletes the selection or the char before the caret.
+    pub fn backspace(&mut self) {
+        if !self.delete_selection() && self.caret > 0 {
+            self.remove(self.caret - 1..self.caret);
+        }
+    }
+
+    /// Deletes the selection or the char after the caret.
+    pub fn delete(&mut self) {
+        if !self.delete_selection() && self.caret < self.len() {
+            self.remove(self.caret..self.caret + 1);
+        }
+    }
+
+    fn delete_selection(&mut self) -> bool {
+        match self.selection() {
+            Some(range) => {
+                self.remove(range);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn remove(&mut self, range: Range<usize>) {
+        let start = range.start;
+
+        self.text.replace_range(self.byte_range(range), "");
+        self.move_to(start, false);
+    }
+
+    fn byte_index(&self, index: usize) -> usize {
+        self.text
+            .char_indices()
+            .nth(index)
+            .map_or(self.text.len(), |(i, _)| i)
+    }
+
+    fn byte_range(&self, range: Range<usize>) -> Range<usize> {
+        self.byte_index(range.start)..self.byte_index(range.end)
+    }
+}
+
+fn is_word(c: char) -> bool { c.is_alphanumeric() || c == '_' }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edit() {
+        let mut edit = TextEdit::new("Привет");
+        assert_eq!(edit.caret(), 6);
+
+        edit.insert(", мир");
+        assert_eq!(edit.text(), "Привет, мир");
+
+        edit.backspace();
+        edit.left(false);
+        edit.delete();
+        assert_eq!(edit.text(), "Привет, м");
+        assert_eq!(edit.caret(), 9);
+
+        edit.move_to(0, false);
+        edit.delete();
+        edit.backspace();
+        assert_eq!(edit.text(), "ривет, м");
+
+        edit.insert("a\nb");
+        assert_eq!(edit.text(), "abривет, м");
+    }
+
+    #[test]
+    fn selection() {
+        let mut edit = TextEdit::new("hello world");
+
+        edit.move_to(2, false);
+        edit.right(true);
+        edit.right(true);
+        assert_eq!(edit.selection(), Some(2..4));
+        assert_eq!(edit.selected_text(), "ll");
+
+        edit.left(false);
+        assert_eq!(edit.caret(), 2);
+        assert_eq!(edit.selection(), None);
+
+        edit.end(true);
+        edit.insert("p!");
+        assert_eq!(edit.text(), "hep!");
+
+        edit.select_all();
+        edit.backspace();
+        assert!(edit.is_empty());
+    }
+
+    #[test]
+    fn words() {
+        let mut edit = TextEdit::new("one, two_2  three");
+
+        edit.word_left(false);
+        assert_eq!(edit.caret(), 12);
+        edit.word_left(false);
+        assert_eq!(edit.caret(), 5);
+        edit.word_left(true);
+        assert_eq!(edit.selected_text(), "one, ");
+
+        edit.word_right(false);
+        assert_eq!(edit.caret(), 3);
+        edit.word_right(false);
+        assert_eq!(edit.caret(), 10);
+    }
+
+    #[test]
+    fn lines() {
+        let mut edit = TextEdit::multiline("ab\ncd\n");
+
+        assert_eq!(edit.line_start(4), 3);
+        assert_eq!(edit.line_end(4), 5);
+        assert_eq!(edit.line_start(6), 6);
+        assert_eq!(edit.line_end(6), 6);
+
+        edit.move_to(4, false);
+        edit.home(true);
+        assert_eq!(edit.selected_text(), "c");
+
+        edit.end(false);
+        edit.insert("\n");
+        assert_eq!(edit.text(), "ab\ncd\n\n");
+    }
+}
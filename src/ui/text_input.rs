@@ -0,0 +1,488 @@
+use std::cell::{Cell, RefCell, RefMut};
+
+use super::{
+    focus::Focusable,
+    text_edit::TextEdit,
+};
+use crate::{
+    common::{
+        Color,
+        Insets,
+        Position,
+        Rect,
+        Vec2d,
+    },
+    graphics::{
+        Render,
+        Draw,
+        DrawParameters,
+        FontStyle,
+        Metrics,
+    },
+    input::{
+        Event,
+        Input,
+        Key,
+        MouseButton,
+        TabStop,
+    },
+};
+
+/// The editable text field.
+///
+/// It's focused by a click or by Tab and reads the input of the `Render` while drawing.
+/// The text colour is inherited, the caret has the same colour.
+#[derive(Debug)]
+pub struct TextInput {
+    edit: RefCell<TextEdit>,
+    width: i32,
+    lines: i32,
+    padding: Insets<i32>,
+    background: Color,
+    selection: Color,
+    /// The scroll of the text, in pixels by `x` and in lines by `y`.
+    scroll: Cell<Vec2d<i32>>,
+    focus: Focusable,
+    dragging: Cell<bool>,
+}
+
+/// Where the text is drawn.
+struct View {
+    rect: Rect<i32>,
+    inner: Rect<i32>,
+    glyph_height: i32,
+    line_height: i32,
+    scroll: Vec2d<i32>,
+}
+
+impl View {
+    fn line_bot(&self, line: i32) -> i32 {
+        self.inner.top() - (line - self.scroll.y) * self.line_height - self.glyph_height
+    }
+
+    /// Returns the index of the caret nearest to the point.
+    fn index_at(&self, carets: &[Vec2d<i32>], point: Vec2d<i32>) -> usize {
+        let last_line = carets.last().map_or(0, |caret| caret.y);
+        let line = (self.inner.top() - 1 - point.y).div_euclid(self.line_height) + self.scroll.y;
+
+        nearest(carets, line.clamp(0, last_line), point.x - self.inner.x + self.scroll.x)
+    }
+}
+
+/// Returns the index of the caret at the `line` nearest to the `x`.
+fn nearest(carets: &[Vec2d<i32>], line: i32, x: i32) -> usize {
+    carets
+        .iter()
+        .enumerate()
+        .filter(|(_, caret)| caret.y == line)
+        .min_by_key(|(_, caret)| (caret.x - x).abs())
+        .map_or(0, |(i, _)| i)
+}
+
+#[allow(dead_code)]
+impl TextInput {
+    /// Creates the single line field of the `width` in pixels.
+    pub fn new(width: i32) -> Self { TextInput::with_edit(TextEdit::default(), width, 1) }
+
+    /// Creates the field that shows `lines` lines.
+    pub fn multiline(width: i32, lines: i32) -> Self {
+        TextInput::with_edit(TextEdit::multiline(""), width, lines.max(1))
+    }
+
+    fn with_edit(edit: TextEdit, width: i32, lines: i32) -> Self {
+        TextInput {
+            edit: RefCell::new(edit),
+            width,
+            lines,
+            padding: Insets::uniform(1),
+            background: Color::rgb(0.1, 0.1, 0.1),
+            selection: Color::rgb(0.2, 0.3, 0.6),
+            scroll: Cell::new(Vec2d::default()),
+            focus: Focusable::default(),
+            dragging: Cell::new(false),
+        }
+    }
+
+    pub fn with_text<S>(self, text: S) -> Self
+        where
+            S: Into<String>,
+    {
+        self.set_text(text);
+        self
+    }
+
+    pub fn padding<I>(mut self, padding: I) -> Self
+        where
+            I: Into<Insets<i32>>,
+    {
+        self.padding = padding.into();
+        self
+    }
+
+    pub fn background(mut self, color: Color) -> Self {
+        self.background = color;
+        self
+    }
+
+    pub fn selection_color(mut self, color: Color) -> Self {
+        self.selection = color;
+        self
+    }
+
+    /// Sets the order of Tab stops. Negative fields are skipped by Tab.
+    pub fn tab_index(mut self, index: i32) -> Self {
+        self.focus.set_stop(TabStop { index, ..self.focus.stop() });
+        self
+    }
+
+    pub fn text(&self) -> String { self.edit.borrow().text().into() }
+
+    pub fn set_text<S>(&self, text: S)
+        where
+            S: Into<String>,
+    { self.edit.borrow_mut().set_text(text) }
+
+    /// Gives access to the caret and the selection.
+    pub fn edit(&self) -> RefMut<'_, TextEdit> { self.edit.borrow_mut() }
+
+    pub fn is_focused(&self) -> bool { self.focus.is_focused() }
+
+    pub fn set_focused(&self, focused: bool) { self.focus.request(focused) }
+
+    fn view(&self, metrics: &Metrics, rect: Rect<i32>) -> View {
+        View {
+            rect,
+            inner: rect.inset(self.padding),
+            glyph_height: metrics.font().default_size().height(),
+            line_height: metrics.font().new_line_height(),
+            scroll: self.scroll.get(),
+        }
+    }
+
+    fn handle(&self, input: &Input, metrics: &Metrics, style: &FontStyle, view: &View) {
+        let mut edit = self.edit.borrow_mut();
+        let modifiers = input.state().modifiers();
+        let (select, word) = (modifiers.shift, modifiers.ctrl);
+
+        for event in input.events() {
+            match event {
+                Event::MouseDown(MouseButton::Left, pos) => {
+                    let inside = view.rect.intersects_point(pos);
+
+                    self.focus.set(inside);
+                    self.dragging.set(inside);
+
+                    if inside {
+                        let carets = metrics.carets(edit.text(), style);
+                        edit.move_to(view.index_at(&carets, pos), select);
+                    }
+                }
+                Event::MouseMove(pos) if self.dragging.get() => {
+                    let carets = metrics.carets(edit.text(), style);
+                    edit.move_to(view.index_at(&carets, pos), true);
+                }
+                Event::MouseUp(MouseButton::Left, _) => self.dragging.set(false),
+                _ if !self.is_focused() => (),
+                Event::Text(c) => edit.insert(c.encode_utf8(&mut [0; 4])),
+                Event::KeyDown(key) => match key {
+                    Key::Left if word => edit.word_left(select),
+                    Key::Left => edit.left(select),
+                    Key::Right if word => edit.word_right(select),
+                    Key::Right => edit.right(select),
+                    Key::Home if word => edit.move_to(0, select),
+                    Key::Home => edit.home(select),
+                    Key::End if word => {
+                        let len = edit.len();
+                        edit.move_to(len, select)
+                    }
+                    Key::End => edit.end(select),
+                    Key::Up | Key::Down if edit.is_multiline() => {
+                        let carets = metrics.carets(edit.text(), style);
+                        let caret = carets[edit.caret()];
+                        let line = if key == Key::Up { caret.y - 1 } else { caret.y + 1 };
+
+                        let index = match line {
+                            l if l < 0 => 0,
+                            l if l > carets[carets.len() - 1].y => edit.len(),
+                            l => nearest(&carets, l, caret.x),
+                        };
+
+                        edit.move_to(index, select);
+                    }
+                    Key::Back => {
+                        if word && edit.selection().is_none() {
+                            edit.word_left(true);
+                        }
+
+                        edit.backspace();
+                    }
+                    Key::Delete => {
+                        if word && edit.selection().is_none() {
+                            edit.word_right(true);
+                        }
+
+                        edit.delete();
+                    }
+                    Key::Return | Key::NumpadEnter if edit.is_multiline() => edit.insert("\n"),
+                    Key::A if word => edit.select_all(),
+                    _ => (),
+                }
+                _ => (),
+            }
+        }
+    }
+
+    /// Scrolls the text so that the caret is visible.
+    fn scroll_to_caret(&self, carets: &[Vec2d<i32>], caret: usize, inner: Rect<i32>) {
+        let text_width = carets.iter().map(|caret| caret.x).max().unwrap_or(0);
+        let caret = carets[caret];
+        let mut scroll = self.scroll.get();
+
+        // The caret is 1 pixel wide
+        scroll.x = scroll.x
+            .max(caret.x - inner.width + 1)
+            .min(caret.x)
+            .min(text_width - inner.width + 1)
+            .max(0);
+
+        scroll.y = scroll.y
+            .max(caret.y - self.lines + 1)
+            .min(caret.y)
+            .max(0);
+
+        self.scroll.set(scroll);
+    }
+}
+
+impl Draw for TextInput {
+    fn draw(&self, render: &mut Render, params: DrawParameters) {
+        let rect = params.render_rect(self.measure(render.metrics(), &params));
+
+        let slot = self.focus.begin(render, true);
+        let view = self.view(render.metrics(), rect);
+        self.handle(render.input(), render.metrics(), &params.font_style, &view);
+
+        let edit = self.edit.borrow();
+        let carets = render.metrics().carets(edit.text(), &params.font_style);
+        self.scroll_to_caret(&carets, edit.caret(), view.inner);
+        let view = self.view(render.metrics(), rect);
+
+        render.fill_rect(rect, self.background);
+
+        let chars: Vec<char> = edit.text().chars().collect();
+        let selection = edit.selection().unwrap_or(0..0);
+        let left = view.inner.x - view.scroll.x;
+
+        render.push_clip(view.inner);
+
+        for line in view.scroll.y..view.scroll.y + self.lines {
+            let start = match carets.iter().position(|caret| caret.y == line) {
+                Some(start) => start,
+                None => break,
+            };
+
+            // The index of the new line or the end of the text
+            let end = edit.line_end(start);
+            let bot = view.line_bot(line);
+
+            if selection.start <= end && start < selection.end {
+                let from = carets[selection.start.max(start)].x;
+                let mut to = carets[selection.end.min(end)].x;
+
+                // The selected new line
+                if selection.end > end {
+                    to += view.glyph_height / 2;
+                }
+
+                let selected = Rect::new((left + from, bot), (to - from, view.glyph_height));
+                render.fill_rect(selected, self.selection);
+            }
+
+            let text: String = chars[start..end].iter().collect();
+
+            let mut line_params = params;
+            line_params.frame = Rect::new((left, bot), (0, 0));
+            line_params.position = Position::LeftBot(0, 0);
+
+            text.draw(render, line_params);
+        }
+
+        let caret = carets[edit.caret()];
+        if self.is_focused() && (view.scroll.y..view.scroll.y + self.lines).contains(&caret.y) {
+            let caret = Rect::new((left + caret.x, view.line_bot(caret.y)), (1, view.glyph_height));
+            render.fill_rect(caret, params.color);
+        }
+
+        render.pop_clip();
+
+        self.focus.end(render, slot, rect);
+    }
+
+    fn measure(&self, metrics: &Metrics, _: &DrawParameters) -> Vec2d<i32> {
+        let font = metrics.font();
+        let height = font.default_size().height() + (self.lines - 1) * font.new_line_height();
+
+        Vec2d::new(self.width, height + self.padding.vertical())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::Modifiers;
+
+    const CTRL: Modifiers = Modifiers { shift: false, ctrl: true, alt: false, logo: false };
+    const SHIFT: Modifiers = Modifiers { shift: true, ctrl: false, alt: false, logo: false };
+
+    fn focused(field: TextInput, text: &str) -> TextInput {
+        field.focus.set(true);
+        field.with_text(text)
+    }
+
+    /// Handles events and scrolls to the caret as the drawing does.
+    fn frame(field: &TextInput, modifiers: Modifiers, events: &[Event]) {
+        let metrics = Metrics::test();
+        let style = FontStyle::default();
+        let params = DrawParameters::new(Rect::new((0, 0), (100, 100)));
+        let view = field.view(&metrics, params.render_rect(field.measure(&metrics, &params)));
+
+        let mut input = Input::new(100, 1);
+        input.set_modifiers(modifiers);
+        events.iter().for_each(|&event| input.push(event));
+
+        field.handle(&input, &metrics, &style, &view);
+
+        let edit = field.edit.borrow();
+        field.scroll_to_caret(&metrics.carets(edit.text(), &style), edit.caret(), view.inner);
+    }
+
+    fn caret(field: &TextInput) -> usize { field.edit.borrow().caret() }
+
+    #[test]
+    fn lines() {
+        // Carets are 5 pixels apart
+        let field = focused(TextInput::multiline(40, 3), "abc\nd\nefgh");
+        let key = |key| frame(&field, Modifiers::default(), &[Event::KeyDown(key)]);
+
+        let moves: Vec<_> = [Key::Up, Key::Up, Key::Up, Key::Down, Key::Down, Key::Down]
+            .iter()
+            .map(|&k| {
+                key(k);
+                caret(&field)
+            })
+            .collect();
+        assert_eq!(moves, [5, 1, 0, 4, 6, 10]);
+
+        frame(&field, SHIFT, &[Event::KeyDown(Key::Up)]);
+        assert_eq!(field.edit.borrow().selected_text(), "\nefgh");
+
+        // Arrows don't leave the single line
+        let field = focused(TextInput::new(40), "abc");
+        frame(&field, Modifiers::default(), &[Event::KeyDown(Key::Up)]);
+        assert_eq!(caret(&field), 3);
+    }
+
+    #[test]
+    fn delete_words() {
+        let field = focused(TextInput::new(100), "hello big world");
+
+        frame(&field, CTRL, &[Event::KeyDown(Key::Back)]);
+        assert_eq!(field.text(), "hello big ");
+
+        frame(&field, CTRL, &[Event::KeyDown(Key::Home), Event::KeyDown(Key::Delete)]);
+        assert_eq!(field.text(), " big ");
+        assert_eq!(caret(&field), 0);
+
+        // Only the selection is deleted
+        frame(&field, SHIFT, &[Event::KeyDown(Key::Right), Event::KeyDown(Key::Right)]);
+        frame(&field, CTRL, &[Event::KeyDown(Key::Delete)]);
+        assert_eq!(field.text(), "ig ");
+
+        frame(&field, CTRL, &[Event::KeyDown(Key::A), Event::KeyDown(Key::Back)]);
+        assert_eq!(field.text(), "");
+    }
+
+    #[test]
+    fn enter() {
+        let field = focused(TextInput::new(100), "ab");
+        frame(&field, Modifiers::default(), &[Event::KeyDown(Key::Return)]);
+        assert_eq!(field.text(), "ab");
+
+        let field = focused(TextInput::multiline(100, 3), "ab");
+        frame(&field, Modifiers::default(), &[
+            Event::KeyDown(Key::Return),
+            Event::KeyDown(Key::NumpadEnter),
+        ]);
+        assert_eq!(field.text(), "ab\n\n");
+
+        // The unfocused field ignores keys
+        field.focus.set(false);
+        frame(&field, Modifiers::default(), &[Event::KeyDown(Key::Return), Event::Text('c')]);
+        assert_eq!(field.text(), "ab\n\n");
+    }
+
+    #[test]
+    fn replace_selection() {
+        let field = focused(TextInput::new(100), "hello world");
+
+        let left = Event::KeyDown(Key::Left);
+        frame(&field, SHIFT, &[left, left, left, left, left]);
+        frame(&field, Modifiers::default(), &[Event::Text('x'), Event::Text('y')]);
+        assert_eq!(field.text(), "hello xy");
+        assert_eq!(caret(&field), 8);
+    }
+
+    #[test]
+    fn scroll() {
+        // 18 pixels inside, the text is 40 pixels wide
+        let field = focused(TextInput::new(20), "abcdefgh");
+
+        frame(&field, Modifiers::default(), &[]);
+        assert_eq!(field.scroll.get(), Vec2d::new(23, 0));
+
+        frame(&field, Modifiers::default(), &[Event::KeyDown(Key::Left)]);
+        assert_eq!(field.scroll.get(), Vec2d::new(23, 0));
+
+        frame(&field, Modifiers::default(), &[Event::KeyDown(Key::Home)]);
+        assert_eq!(field.scroll.get(), Vec2d::new(0, 0));
+
+        // The caret at the last of 4 lines, 2 of them are shown
+        let field = focused(TextInput::multiline(20, 2), "a\nb\nc\nd");
+
+        frame(&field, Modifiers::default(), &[]);
+        assert_eq!(field.scroll.get(), Vec2d::new(0, 2));
+
+        frame(&field, Modifiers::default(), &[Event::KeyDown(Key::Up)]);
+        assert_eq!(field.scroll.get(), Vec2d::new(0, 2));
+
+        frame(&field, CTRL, &[Event::KeyDown(Key::Home)]);
+        assert_eq!(field.scroll.get(), Vec2d::new(0, 0));
+    }
+
+    #[test]
+    fn index_at() {
+        // Two lines of 3 and 1 chars, 4 pixels each, with 10 pixel lines
+        let carets: Vec<_> = [(0, 0), (4, 0), (8, 0), (12, 0), (0, 1), (4, 1)]
+            .iter()
+            .map(|&caret| Vec2d::from(caret))
+            .collect();
+
+        let view = View {
+            rect: Rect::new((0, 0), (20, 20)),
+            inner: Rect::new((0, 0), (20, 20)),
+            glyph_height: 8,
+            line_height: 10,
+            scroll: Vec2d::new(0, 0),
+        };
+
+        assert_eq!(view.index_at(&carets, Vec2d::new(0, 19)), 0);
+        assert_eq!(view.index_at(&carets, Vec2d::new(5, 15)), 1);
+        assert_eq!(view.index_at(&carets, Vec2d::new(50, 15)), 3);
+        assert_eq!(view.index_at(&carets, Vec2d::new(3, 5)), 5);
+        assert_eq!(view.index_at(&carets, Vec2d::new(3, -50)), 5);
+        assert_eq!(view.index_at(&carets, Vec2d::new(3, 50)), 1);
+
+        let view = View { scroll: Vec2d::new(4, 1), ..view };
+        assert_eq!(view.index_at(&carets, Vec2d::new(0, 15)), 5);
+    }
+}
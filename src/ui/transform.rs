@@ -0,0 +1,233 @@
+use crate::{
+    common::{
+        Rect,
+        Vec2d,
+    },
+    graphics::{
+        self,
+        Render,
+        Draw,
+        DrawParameters,
+        Metrics,
+        HitTest,
+    },
+};
+
+/// Draws the content transformed by the matrix in UI pixels.
+///
+/// Transforms don't change the layout, so the content is measured as is.
+/// `Render::input` maps the pointer back, so widgets inside react where they are drawn.
+#[derive(Debug)]
+pub struct Transform<U> {
+    matrix: glm::Mat4,
+    ui: U,
+}
+
+#[allow(dead_code)]
+impl<U> Transform<U>
+    where
+        U: Draw,
+{
+    pub fn new(matrix: glm::Mat4, ui: U) -> Self { Transform { matrix, ui } }
+
+    pub fn translate<D>(delta: D, ui: U) -> Self
+        where
+            D: Into<Vec2d<f32>>,
+    { Transform::new(graphics::translation(delta), ui) }
+
+    pub fn matrix(&self) -> &glm::Mat4 { &self.matrix }
+
+    pub fn set_matrix(&mut self, matrix: glm::Mat4) { self.matrix = matrix }
+}
+
+impl<U> Draw for Transform<U>
+    where
+        U: Draw,
+{
+    fn draw(&self, render: &mut Render, params: DrawParameters) {
+        render.push_transform(&self.matrix);
+        self.ui.draw(render, params);
+        render.pop_transform();
+    }
+
+    fn measure(&self, metrics: &Metrics, params: &DrawParameters) -> Vec2d<i32> {
+        self.ui.measure(metrics, params)
+    }
+
+    fn hit(&self, hits: &mut HitTest, params: DrawParameters) {
+        hits.push_transform(&self.matrix);
+        self.ui.hit(hits, params);
+        hits.pop_transform();
+    }
+}
+
+/// Returns the point of the content rect at the pivot, where (0, 0) is the bottom left corner
+/// and (1, 1) is the top right one.
+fn pivot_point<U>(ui: &U, pivot: Vec2d<f32>, metrics: &Metrics, params: &DrawParameters)
+                  -> Vec2d<f32>
+    where
+        U: Draw,
+{
+    let rect: Rect<f32> = params.render_rect(ui.measure(metrics, params)).cast();
+    Vec2d::new(rect.x + rect.width * pivot.x, rect.y + rect.height * pivot.y)
+}
+
+/// Scales the content around the pivot, the center by default.
+#[derive(Debug)]
+pub struct Scale<U> {
+    scale: Vec2d<f32>,
+    pivot: Vec2d<f32>,
+    ui: U,
+}
+
+#[allow(dead_code)]
+impl<U> Scale<U>
+    where
+        U: Draw,
+{
+    pub fn new(scale: f32, ui: U) -> Self { Scale::xy((scale, scale), ui) }
+
+    pub fn xy<S>(scale: S, ui: U) -> Self
+        where
+            S: Into<Vec2d<f32>>,
+    {
+        Scale {
+            scale: scale.into(),
+            pivot: Vec2d::new(0.5, 0.5),
+            ui,
+        }
+    }
+
+    /// Sets the pivot relative to the content, (0, 0) is the bottom left corner.
+    pub fn pivot<P>(mut self, pivot: P) -> Self
+        where
+            P: Into<Vec2d<f32>>,
+    {
+        self.pivot = pivot.into();
+        self
+    }
+
+    pub fn scale(&self) -> Vec2d<f32> { self.scale }
+
+    pub fn set_scale<S>(&mut self, scale: S)
+        where
+            S: Into<Vec2d<f32>>,
+    { self.scale = scale.into() }
+
+    fn matrix(&self, metrics: &Metrics, params: &DrawParameters) -> glm::Mat4 {
+        let scaling = glm::scaling(&glm::vec3(self.scale.x, self.scale.y, 1.0));
+        graphics::around(pivot_point(&self.ui, self.pivot, metrics, params), &scaling)
+    }
+}
+
+impl<U> Draw for Scale<U>
+    where
+        U: Draw,
+{
+    fn draw(&self, render: &mut Render, params: DrawParameters) {
+        render.push_transform(&self.matrix(render.metrics(), &params));
+        self.ui.draw(render, params);
+        render.pop_transform();
+    }
+
+    fn measure(&self, metrics: &Metrics, params: &DrawParameters) -> Vec2d<i32> {
+        self.ui.measure(metrics, params)
+    }
+
+    fn hit(&self, hits: &mut HitTest, params: DrawParameters) {
+        hits.push_transform(&self.matrix(hits.metrics(), &params));
+        self.ui.hit(hits, params);
+        hits.pop_transform();
+    }
+}
+
+/// Rotates the content counterclockwise around the pivot, the center by default.
+#[derive(Debug)]
+pub struct Rotate<U> {
+    degrees: f32,
+    pivot: Vec2d<f32>,
+    ui: U,
+}
+
+#[allow(dead_code)]
+impl<U> Rotate<U>
+    where
+        U: Draw,
+{
+    pub fn new(degrees: f32, ui: U) -> Self {
+        Rotate {
+            degrees,
+            pivot: Vec2d::new(0.5, 0.5),
+            ui,
+        }
+    }
+
+    /// Sets the pivot relative to the content, (0, 0) is the bottom left corner.
+    pub fn pivot<P>(mut self, pivot: P) -> Self
+        where
+            P: Into<Vec2d<f32>>,
+    {
+        self.pivot = pivot.into();
+        self
+    }
+
+    pub fn degrees(&self) -> f32 { self.degrees }
+
+    pub fn set_degrees(&mut self, degrees: f32) { self.degrees = degrees }
+
+    fn matrix(&self, metrics: &Metrics, params: &DrawParameters) -> glm::Mat4 {
+        let rotation = glm::rotation(self.degrees.to_radians(), &glm::vec3(0.0, 0.0, 1.0));
+        graphics::around(pivot_point(&self.ui, self.pivot, metrics, params), &rotation)
+    }
+}
+
+impl<U> Draw for Rotate<U>
+    where
+        U: Draw,
+{
+    fn draw(&self, render: &mut Render, params: DrawParameters) {
+        render.push_transform(&self.matrix(render.metrics(), &params));
+        self.ui.draw(render, params);
+        render.pop_transform();
+    }
+
+    fn measure(&self, metrics: &Metrics, params: &DrawParameters) -> Vec2d<i32> {
+        self.ui.measure(metrics, params)
+    }
+
+    fn hit(&self, hits: &mut HitTest, params: DrawParameters) {
+        hits.push_transform(&self.matrix(hits.metrics(), &params));
+        self.ui.hit(hits, params);
+        hits.pop_transform();
+    }
+}
+
+impl<U> std::ops::Deref for Transform<U> {
+    type Target = U;
+
+    fn deref(&self) -> &Self::Target { &self.ui }
+}
+
+impl<U> std::ops::DerefMut for Transform<U> {
+    fn deref_mut(&mut self) -> &mut Self::Target { &mut self.ui }
+}
+
+impl<U> std::ops::Deref for Scale<U> {
+    type Target = U;
+
+    fn deref(&self) -> &Self::Target { &self.ui }
+}
+
+impl<U> std::ops::DerefMut for Scale<U> {
+    fn deref_mut(&mut self) -> &mut Self::Target { &mut self.ui }
+}
+
+impl<U> std::ops::Deref for Rotate<U> {
+    type Target = U;
+
+    fn deref(&self) -> &Self::Target { &self.ui }
+}
+
+impl<U> std::ops::DerefMut for Rotate<U> {
+    fn deref_mut(&mut self) -> &mut Self::Target { &mut self.ui }
+}